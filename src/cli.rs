@@ -0,0 +1,470 @@
+use crate::maze::animation::{delay, start_virtual_clock, stop_virtual_clock, Delay};
+use crate::maze::asciicast::AsciicastRecorder;
+use crate::maze::benchmark::{BenchmarkResultCollection, NullWriter};
+use crate::maze::draw::TerminalRenderer;
+use crate::maze::event::NullEventHandler;
+use crate::maze::generator::{
+    Braid, Eller, MazeGenerationAlgorithms, MazeGenerator, Terrain,
+    GROWING_TREE_MIXED_RATIO_DEFAULT,
+};
+use crate::maze::gif::{GifRecorder, GIF_CELL_SIZE_DEFAULT};
+use crate::maze::json_format::{
+    maze_from_json, maze_to_json, solver_result_to_json, SolverResultJson,
+};
+use crate::maze::maze::{Maze, MAZE_EDGE_LENGTH_MIN};
+use crate::maze::path::{calculate_path_cost, get_solving_sequence};
+use crate::maze::png::{maze_to_png, PNG_SCALE_DEFAULT};
+use crate::maze::solver::{MazeSolver, MazeSolvingAlgorithms};
+use crate::maze::svg::maze_to_svg;
+use crate::maze::text_format::{maze_from_text, maze_to_text, write_rows_as_text};
+use clap::{Parser, Subcommand, ValueEnum};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs;
+use std::io::{BufWriter, ErrorKind, Read, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+// Without a subcommand, the interactive terminal application is started.
+#[derive(Parser)]
+#[command(about = "Maze-generation and maze-solving written in Rust.")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Record the session in the terminal application as asciicast (asciinema v2) into this file.
+    #[arg(long)]
+    pub record: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Svg,
+    Png,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate a maze and write it as text grid.
+    Generate {
+        /// The generation algorithm, e.g. "wilson" or "binary-tree-ne".
+        #[arg(long, default_value = "kruskal", value_parser = parse_generation_algorithm)]
+        algo: MazeGenerationAlgorithms,
+        /// The size of the maze as WIDTHxHEIGHT (even edge lengths are reduced by one).
+        #[arg(long, default_value = "21x21", value_parser = parse_size)]
+        size: (usize, usize),
+        /// The seed of the maze (random if not given).
+        #[arg(long)]
+        seed: Option<u64>,
+        /// The fraction of dead ends to remove (0 = perfect maze, 1 = no dead ends).
+        #[arg(long, default_value_t = 0.0, value_parser = parse_fraction)]
+        braid: f64,
+        /// The probability of choosing the newest cell (growing-tree-mixed only).
+        #[arg(long, default_value_t = GROWING_TREE_MIXED_RATIO_DEFAULT, value_parser = parse_fraction)]
+        ratio: f64,
+        /// Cover the maze with terrain (grass, mud and water) that is expensive to walk through.
+        #[arg(long)]
+        terrain: bool,
+        /// The file to write the maze to (stdout if not given).
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// The format of the maze.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// The number of pixels per field of the maze (png only).
+        #[arg(long, default_value_t = PNG_SCALE_DEFAULT)]
+        scale: usize,
+    },
+    /// Solve a maze given as text grid.
+    Solve {
+        /// The solving algorithm, e.g. "astar" or "bfs".
+        #[arg(long, default_value = "bfs", value_parser = parse_solving_algorithm)]
+        algo: MazeSolvingAlgorithms,
+        /// The file to read the maze from (stdin if not given). Text and JSON are detected automatically.
+        file: Option<PathBuf>,
+        /// The format of the result (svg / png: the maze with the path and the inspected cells).
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// The number of pixels per field of the maze (png only).
+        #[arg(long, default_value_t = PNG_SCALE_DEFAULT)]
+        scale: usize,
+    },
+    /// Record the animations of generating (and solving) a maze into an animated GIF or an
+    /// asciicast (if the file ends with .cast).
+    Record {
+        /// The generation algorithm, e.g. "wilson" or "binary-tree-ne".
+        #[arg(long = "gen", default_value = "kruskal", value_parser = parse_generation_algorithm)]
+        generation_algorithm: MazeGenerationAlgorithms,
+        /// The solving algorithm, e.g. "astar" or "bfs" (only generation if not given).
+        #[arg(long = "solve", value_parser = parse_solving_algorithm)]
+        solving_algorithm: Option<MazeSolvingAlgorithms>,
+        /// The size of the maze as WIDTHxHEIGHT (even edge lengths are reduced by one).
+        #[arg(long, default_value = "41x41", value_parser = parse_size)]
+        size: (usize, usize),
+        /// The seed of the maze (random if not given).
+        #[arg(long)]
+        seed: Option<u64>,
+        /// The fraction of dead ends to remove (0 = perfect maze, 1 = no dead ends).
+        #[arg(long, default_value_t = 0.0, value_parser = parse_fraction)]
+        braid: f64,
+        /// The probability of choosing the newest cell (growing-tree-mixed only).
+        #[arg(long, default_value_t = GROWING_TREE_MIXED_RATIO_DEFAULT, value_parser = parse_fraction)]
+        ratio: f64,
+        /// Cover the maze with terrain (grass, mud and water) that is expensive to walk through.
+        #[arg(long)]
+        terrain: bool,
+        /// The number of pixels per field of the maze (gif only).
+        #[arg(long, default_value_t = GIF_CELL_SIZE_DEFAULT)]
+        scale: usize,
+        /// The file to write the recording to (.gif or .cast).
+        out: PathBuf,
+    },
+    /// Benchmark all generation and solving algorithms and write the results into a csv file.
+    Bench {
+        /// The fraction of dead ends to remove (0 = perfect maze, 1 = no dead ends).
+        #[arg(long, default_value_t = 0.0, value_parser = parse_fraction)]
+        braid: f64,
+        /// The probability of choosing the newest cell (growing-tree-mixed only).
+        #[arg(long, default_value_t = GROWING_TREE_MIXED_RATIO_DEFAULT, value_parser = parse_fraction)]
+        ratio: f64,
+    },
+}
+
+fn parse_generation_algorithm(name: &str) -> Result<MazeGenerationAlgorithms, String> {
+    MazeGenerationAlgorithms::from_command_line_name(name).ok_or_else(|| {
+        format!(
+            "unknown generation algorithm, possible values: {}",
+            MazeGenerationAlgorithms::all()
+                .iter()
+                .map(|algorithm| algorithm.to_command_line_name())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    })
+}
+
+fn parse_solving_algorithm(name: &str) -> Result<MazeSolvingAlgorithms, String> {
+    MazeSolvingAlgorithms::from_command_line_name(name).ok_or_else(|| {
+        format!(
+            "unknown solving algorithm, possible values: {}",
+            MazeSolvingAlgorithms::all()
+                .iter()
+                .map(|algorithm| algorithm.to_command_line_name())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )
+    })
+}
+
+fn parse_fraction(fraction: &str) -> Result<f64, String> {
+    let fraction: f64 = fraction
+        .parse()
+        .map_err(|_| format!("invalid fraction '{}'", fraction))?;
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(String::from("the fraction needs to be between 0 and 1"));
+    }
+    Ok(fraction)
+}
+
+fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    let (width, height) = size
+        .split_once('x')
+        .ok_or_else(|| String::from("expected WIDTHxHEIGHT, e.g. 101x61"))?;
+    let width: usize = width
+        .parse()
+        .map_err(|_| format!("invalid width '{}'", width))?;
+    let height: usize = height
+        .parse()
+        .map_err(|_| format!("invalid height '{}'", height))?;
+    if width < MAZE_EDGE_LENGTH_MIN || height < MAZE_EDGE_LENGTH_MIN {
+        return Err(format!(
+            "the width and height need to be at least {}",
+            MAZE_EDGE_LENGTH_MIN
+        ));
+    }
+    Ok((width, height))
+}
+
+// Generates (and solves) the maze with animations. Between them, the finished maze is shown.
+fn record_animations(
+    maze: &mut Maze,
+    generator: &dyn MazeGenerator,
+    seed: u64,
+    solver: Option<&dyn MazeSolver>,
+    screen: &mut dyn Write,
+) {
+    let mut renderer = TerminalRenderer::new(screen, true);
+    maze.generate(generator, seed, &mut renderer);
+    delay(Delay::VeryLong);
+    if let Some(solver) = solver {
+        maze.solve(solver, &mut renderer);
+        delay(Delay::VeryLong);
+    }
+}
+
+// Writing into a closed pipe (e.g. `mazes generate | head`) ends the output early, but is no
+// error.
+fn check_stdout(result: std::io::Result<()>) -> Result<(), String> {
+    match result {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe => {
+            Err(format!("Cannot write stdout: {}", error))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn write_stdout(output: &[u8]) -> Result<(), String> {
+    check_stdout(std::io::stdout().lock().write_all(output))
+}
+
+pub fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Generate {
+            algo,
+            size,
+            seed,
+            braid,
+            ratio,
+            terrain,
+            out,
+            format,
+            scale,
+        } => {
+            let seed = seed.unwrap_or_else(rand::random);
+            if matches!(algo, MazeGenerationAlgorithms::Eller)
+                && braid == 0.0
+                && !terrain
+                && format == Format::Text
+            {
+                // Eller's algorithm creates the maze row by row, write every row right away
+                // instead of building the maze first (this keeps only one row in memory). Even
+                // edge lengths are reduced by one, like in Maze::new.
+                let (width, height) = (size.0 - (1 - size.0 % 2), size.1 - (1 - size.1 % 2));
+                let rows = Eller::rows(width, height, StdRng::seed_from_u64(seed));
+                let generator = Eller.to_string();
+                return match out {
+                    Some(path) => fs::File::create(&path)
+                        .map(BufWriter::new)
+                        .and_then(|mut writer| {
+                            write_rows_as_text(&mut writer, rows, width, height, &generator, seed)
+                                .and_then(|_| writer.flush())
+                        })
+                        .map_err(|error| format!("Cannot write {}: {}", path.display(), error)),
+                    None => check_stdout(write_rows_as_text(
+                        &mut std::io::stdout().lock(),
+                        rows,
+                        width,
+                        height,
+                        &generator,
+                        seed,
+                    )),
+                };
+            }
+            let mut maze = Maze::new(size.0, size.1, (1, 1));
+            maze.change_size(size.0, size.1);
+            maze.generate(
+                &*Terrain::wrap(
+                    Braid::wrap(algo.with_mixed_ratio(ratio).to_generator(), braid),
+                    terrain,
+                ),
+                seed,
+                &mut NullEventHandler,
+            );
+            let output = match format {
+                Format::Text => maze_to_text(&maze).into_bytes(),
+                Format::Json => (maze_to_json(&maze) + "\n").into_bytes(),
+                Format::Svg => maze_to_svg(&maze, None, None).into_bytes(),
+                Format::Png => maze_to_png(&maze, None, None, scale)?,
+            };
+            match out {
+                Some(path) => fs::write(&path, output)
+                    .map_err(|error| format!("Cannot write {}: {}", path.display(), error))?,
+                None => write_stdout(&output)?,
+            }
+        }
+        Command::Solve {
+            algo,
+            file,
+            format,
+            scale,
+        } => {
+            let text = match file {
+                Some(path) => fs::read_to_string(&path)
+                    .map_err(|error| format!("Cannot read {}: {}", path.display(), error))?,
+                None => {
+                    let mut text = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut text)
+                        .map_err(|error| format!("Cannot read stdin: {}", error))?;
+                    text
+                }
+            };
+            let mut maze = if text.trim_start().starts_with('{') {
+                maze_from_json(&text)?
+            } else {
+                maze_from_text(&text)?
+            };
+            let solver = algo.to_solver();
+            let (path, number_of_inspected_cells) = maze.solve(&*solver, &mut NullEventHandler);
+            if path.is_empty() {
+                return Err(format!(
+                    "{} found no path from the start to the end position.",
+                    solver.to_string()
+                ));
+            }
+            match format {
+                Format::Text => {}
+                Format::Json => {
+                    return check_stdout(writeln!(
+                        std::io::stdout().lock(),
+                        "{}",
+                        solver_result_to_json(&SolverResultJson::new(
+                            solver.to_string(),
+                            path,
+                            number_of_inspected_cells,
+                            maze.number_of_covered_cells,
+                            maze.inspection_order.clone(),
+                        ))
+                    ));
+                }
+                Format::Svg => {
+                    // The maze with the path and the inspected cells as heatmap.
+                    return write_stdout(
+                        maze_to_svg(&maze, Some(&path), Some(&maze.inspection_order)).as_bytes(),
+                    );
+                }
+                Format::Png => {
+                    write_stdout(&maze_to_png(
+                        &maze,
+                        Some(&path),
+                        Some(&maze.inspection_order),
+                        scale,
+                    )?)?;
+                    return Ok(());
+                }
+            }
+            let solving_sequence: String = get_solving_sequence(&path).iter().collect();
+            let mut stdout = std::io::stdout().lock();
+            check_stdout(
+                writeln!(stdout, "path length: {}", path.len())
+                    .and_then(|_| {
+                        writeln!(stdout, "path cost: {}", calculate_path_cost(&maze, &path))
+                    })
+                    .and_then(|_| match maze.number_of_covered_cells {
+                        // The graph solvers inspect nodes, each one covers its corridors.
+                        Some(number_of_covered_cells) => {
+                            writeln!(stdout, "inspected nodes: {}", number_of_inspected_cells)
+                                .and_then(|_| {
+                                    writeln!(stdout, "covered cells: {}", number_of_covered_cells)
+                                })
+                        }
+                        None => writeln!(stdout, "inspected cells: {}", number_of_inspected_cells),
+                    })
+                    .and_then(|_| {
+                        if solving_sequence.is_empty() {
+                            writeln!(stdout, "No solving sequence available.")
+                        } else {
+                            writeln!(stdout, "solving sequence: {}", solving_sequence)
+                        }
+                    }),
+            )?;
+        }
+        Command::Record {
+            generation_algorithm,
+            solving_algorithm,
+            size,
+            seed,
+            braid,
+            ratio,
+            terrain,
+            scale,
+            out,
+        } => {
+            let mut maze = Maze::new(size.0, size.1, (1, 1));
+            maze.change_size(size.0, size.1);
+            // The maze is drawn where it would be drawn in this terminal (in the top left corner
+            // if the terminal is too small).
+            let (terminal_width, terminal_height) = termion::terminal_size().unwrap_or((0, 0));
+            let (width, height) = (
+                maze.width.max(terminal_width as usize),
+                maze.height.max(terminal_height as usize),
+            );
+            let seed = seed.unwrap_or_else(rand::random);
+            let generator = Terrain::wrap(
+                Braid::wrap(
+                    generation_algorithm.with_mixed_ratio(ratio).to_generator(),
+                    braid,
+                ),
+                terrain,
+            );
+            let solver = solving_algorithm.map(|algorithm| algorithm.to_solver());
+            // Run the animations without sleeping.
+            start_virtual_clock();
+            let result = if out.extension().is_some_and(|extension| extension == "cast") {
+                fs::File::create(&out)
+                    .and_then(|file| {
+                        let mut recorder = AsciicastRecorder::new(
+                            NullWriter,
+                            Box::new(BufWriter::new(file)),
+                            width,
+                            height,
+                            false,
+                        )?;
+                        record_animations(
+                            &mut maze,
+                            &*generator,
+                            seed,
+                            solver.as_deref(),
+                            &mut recorder,
+                        );
+                        recorder.finish()
+                    })
+                    .map_err(|error| format!("Cannot write {}: {}", out.display(), error))
+            } else {
+                let mut recorder = GifRecorder::new(width, height);
+                record_animations(
+                    &mut maze,
+                    &*generator,
+                    seed,
+                    solver.as_deref(),
+                    &mut recorder,
+                );
+                recorder.finish(scale).and_then(|gif| {
+                    fs::write(&out, gif)
+                        .map_err(|error| format!("Cannot write {}: {}", out.display(), error))
+                })
+            };
+            stop_virtual_clock();
+            result?;
+            check_stdout(writeln!(
+                std::io::stdout().lock(),
+                "Recording (seed {}) written to {}.",
+                seed,
+                out.display()
+            ))?;
+        }
+        Command::Bench { braid, ratio } => {
+            let start_time = Instant::now();
+            let mut benchmark_results = BenchmarkResultCollection::new(braid, ratio);
+            loop {
+                let (is_running, progress) = benchmark_results.benchmark_next_chunk();
+                eprint!("\rExecuting benchmark ... ({}%)", progress);
+                if !is_running {
+                    break;
+                }
+            }
+            eprintln!();
+            let csv_filename = benchmark_results.to_csv();
+            let elapsed_time = start_time.elapsed();
+            check_stdout(writeln!(
+                std::io::stdout().lock(),
+                "Benchmark finished after {} min {} s. Results written to {}.",
+                elapsed_time.as_secs() / 60,
+                elapsed_time.as_secs() % 60,
+                csv_filename
+            ))?;
+        }
+    }
+    Ok(())
+}
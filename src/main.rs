@@ -1,12 +1,27 @@
+use clap::Parser;
+use cli::Cli;
+use maze::animation::{
+    change_animation_speed, resume_animations, set_animation_commands, start_virtual_clock,
+    AnimationCommand,
+};
+use maze::asciicast::AsciicastRecorder;
 use maze::benchmark::*;
 use maze::draw::*;
 use maze::generator::*;
 use maze::maze::Maze;
 use maze::maze_collection::MazeCollection;
 use maze::maze_container::MazeContainer;
-use maze::path::get_solving_sequence;
+use maze::path::{calculate_path_cost, get_solving_sequence};
 use maze::solver::*;
-use std::io::{stdin, stdout, Write};
+use maze::step_debugger::StepDebugger;
+use maze::text_format::{maze_from_text, maze_to_text};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs::File;
+use std::io::{stdin, stdout, BufWriter, Write};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
 use std::time::Instant;
 use terminal_ui::{TERMINAL_HEIGHT_MIN, TERMINAL_WIDTH_MIN};
 use termion::event::Key;
@@ -14,10 +29,23 @@ use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 use termion::screen::{IntoAlternateScreen, ToAlternateScreen};
 
+mod cli;
 mod maze;
 mod terminal_ui;
 
+const DEFAULT_MAZE_FILENAME: &str = "maze.txt";
+
 fn main() {
+    // Run a subcommand without the terminal ui (e.g. in scripts).
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        if let Err(error) = cli::run(command) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Check if the terminal is large enough.
     let (terminal_width, terminal_height) = termion::terminal_size().unwrap();
     if terminal_width < TERMINAL_WIDTH_MIN || terminal_height < TERMINAL_HEIGHT_MIN {
@@ -25,16 +53,68 @@ fn main() {
         return;
     }
 
+    // The file the session is recorded to.
+    let recording = match cli.record {
+        Some(path) => match File::create(&path) {
+            Ok(file) => Some(file),
+            Err(error) => {
+                println!("Cannot write {}: {}", path.display(), error);
+                return;
+            }
+        },
+        None => None,
+    };
+
     // Initialize the alternate screen.
-    let stdin = stdin();
-    let mut screen = stdout()
+    let screen = stdout()
         .into_raw_mode()
         .unwrap()
         .into_alternate_screen()
         .unwrap();
+    let mut screen: Box<dyn Write> = match recording {
+        Some(file) => {
+            // The animations are not played while recording, their delays only become part of
+            // the recording.
+            start_virtual_clock();
+            Box::new(
+                AsciicastRecorder::new(
+                    screen,
+                    Box::new(BufWriter::new(file)),
+                    terminal_width as usize,
+                    terminal_height as usize,
+                    true,
+                )
+                .unwrap(),
+            )
+        }
+        None => Box::new(screen),
+    };
     write!(screen, "{}{}", termion::cursor::Hide, ToAlternateScreen).unwrap();
     screen.flush().unwrap();
 
+    // The keys are read in the background, so they can also control a running animation:
+    // + / - change the speed, space pauses and Esc aborts it. Other keys are ignored meanwhile.
+    let (key_sender, key_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for key in stdin().keys() {
+            if key_sender.send(key).is_err() {
+                break;
+            }
+        }
+    });
+    let key_receiver = Rc::new(key_receiver);
+    let animation_keys = key_receiver.clone();
+    set_animation_commands(Box::new(move || loop {
+        match animation_keys.try_recv() {
+            Ok(Ok(Key::Char('+'))) => return Some(AnimationCommand::Faster),
+            Ok(Ok(Key::Char('-'))) => return Some(AnimationCommand::Slower),
+            Ok(Ok(Key::Char(' '))) => return Some(AnimationCommand::TogglePause),
+            Ok(Ok(Key::Esc)) => return Some(AnimationCommand::Abort),
+            Ok(_) => {}
+            Err(_) => return None,
+        }
+    }));
+
     // To toggle visualization options.
     let mut show_graph: bool = false;
     let mut show_background_graph: bool = false;
@@ -42,6 +122,14 @@ fn main() {
     let mut show_background_binary_representation: bool = false;
     let mut show_grid_representation: bool = false;
     let mut animate: bool = false;
+    // The fraction of dead ends that are removed after the generation (0 = perfect maze).
+    let mut dead_end_removal: f64 = 0.0;
+    // Cover the maze with terrain that is expensive to walk through (weighted mazes).
+    let mut terrain: bool = false;
+    // The seed of the current maze. The same seed always leads to the same maze.
+    let mut seed: u64 = rand::random();
+    // Used for the random start and end positions, also derived from the seed.
+    let mut rng = StdRng::seed_from_u64(seed);
 
     // Selected algorithms.
     let mut generation_algorithm = MazeGenerationAlgorithms::Kruskal;
@@ -50,26 +138,29 @@ fn main() {
     // Initialize the maze with the information about its max size.
     let (max_maze_width, max_maze_height) = terminal_ui::get_max_draw_size();
     let mut maze_container =
-        MazeContainer::SingleMaze(Maze::new(max_maze_width, max_maze_height, (1, 1)));
+        MazeContainer::SingleMaze(Box::new(Maze::new(max_maze_width, max_maze_height, (1, 1))));
 
     // Draw terminal ui and the maze.
     terminal_ui::intialize_terminal_ui(&mut screen);
     terminal_ui::print_informations(
         &mut screen,
         maze_container.get_size(),
-        generation_algorithm.to_string(),
+        &generation_algorithm.to_string(),
         solving_algorithm.to_string(),
         0,
         animate,
+        dead_end_removal,
+        terrain,
+        seed,
     );
 
     // Generate the first maze.
     maze_container.generate(
-        match generation_algorithm {
-            MazeGenerationAlgorithms::Kruskal => &Kruskal,
-            MazeGenerationAlgorithms::RecursiveBacktracking => &RecursiveBacktracking,
-            MazeGenerationAlgorithms::Wilson => &Wilson,
-        },
+        &*Terrain::wrap(
+            Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+            terrain,
+        ),
+        seed,
         &mut screen,
         animate,
     );
@@ -83,9 +174,12 @@ fn main() {
     );
 
     // The main loop that keeps the program alive. q breaks it.
-    for c in stdin.keys() {
+    let mut keys = key_receiver.iter();
+    while let Some(c) = keys.next() {
         // Process the input.
         let key = c.unwrap();
+        // An abort only stops the animation that was running.
+        resume_animations();
         match key {
             Key::Char('q') => break,
             Key::Ctrl('l') => {
@@ -94,10 +188,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
                 maze_container.draw(
@@ -121,25 +218,168 @@ fn main() {
                 );
             }
             Key::Char('r') => {
+                // Choose a new seed.
+                seed = rand::random();
+                rng = StdRng::seed_from_u64(seed);
                 // Reset the informations in the UI.
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
                 // Recreate.
                 maze_container.generate(
-                    match generation_algorithm {
-                        MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                        MazeGenerationAlgorithms::RecursiveBacktracking => &RecursiveBacktracking,
-                        MazeGenerationAlgorithms::Wilson => &Wilson,
+                    &*Terrain::wrap(
+                        Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                        terrain,
+                    ),
+                    seed,
+                    &mut screen,
+                    animate,
+                );
+                maze_container.draw(
+                    &mut screen,
+                    show_graph,
+                    show_background_graph,
+                    show_binary_representation,
+                    show_background_binary_representation,
+                    show_grid_representation,
+                );
+            }
+            Key::Char('i') => {
+                // Enter a seed and recreate the maze with it.
+                let input = terminal_ui::read_input(&mut screen, &mut keys, "Enter seed: ");
+                match input.map(|input| input.trim().parse::<u64>()) {
+                    Some(Ok(value)) => seed = value,
+                    Some(Err(_)) => {
+                        terminal_ui::print_solving_sequence(
+                            &mut screen,
+                            String::from("Invalid seed."),
+                        );
+                        continue;
+                    }
+                    None => continue,
+                }
+                rng = StdRng::seed_from_u64(seed);
+                // Reset the informations in the UI.
+                terminal_ui::print_informations(
+                    &mut screen,
+                    maze_container.get_size(),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    0,
+                    animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+                // Recreate.
+                maze_container.generate(
+                    &*Terrain::wrap(
+                        Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                        terrain,
+                    ),
+                    seed,
+                    &mut screen,
+                    animate,
+                );
+                maze_container.draw(
+                    &mut screen,
+                    show_graph,
+                    show_background_graph,
+                    show_binary_representation,
+                    show_background_binary_representation,
+                    show_grid_representation,
+                );
+            }
+            Key::Char('w') => {
+                // Write the maze into a text file. (only for single maze)
+                let MazeContainer::SingleMaze(ref maze) = maze_container else {
+                    continue;
+                };
+                if !maze.is_generated {
+                    continue;
+                }
+                let Some(filename) =
+                    terminal_ui::read_input(&mut screen, &mut keys, "Save maze to: ")
+                else {
+                    continue;
+                };
+                let filename = if filename.trim().is_empty() {
+                    String::from(DEFAULT_MAZE_FILENAME)
+                } else {
+                    String::from(filename.trim())
+                };
+                terminal_ui::print_solving_sequence(
+                    &mut screen,
+                    match std::fs::write(&filename, maze_to_text(maze)) {
+                        Ok(_) => format!("Maze saved to {}.", filename),
+                        Err(error) => format!("Cannot write {}: {}", filename, error),
                     },
+                );
+            }
+            Key::Char('O') => {
+                // Open a maze from a text file (e.g. a hand-drawn one).
+                let Some(filename) =
+                    terminal_ui::read_input(&mut screen, &mut keys, "Open maze from: ")
+                else {
+                    continue;
+                };
+                let filename = if filename.trim().is_empty() {
+                    String::from(DEFAULT_MAZE_FILENAME)
+                } else {
+                    String::from(filename.trim())
+                };
+                let maze = std::fs::read_to_string(&filename)
+                    .map_err(|error| format!("Cannot read {}: {}", filename, error))
+                    .and_then(|text| maze_from_text(&text));
+                let mut maze = match maze {
+                    Ok(maze) => maze,
+                    Err(error) => {
+                        terminal_ui::print_solving_sequence(&mut screen, error);
+                        continue;
+                    }
+                };
+                if maze.width > max_maze_width || maze.height > max_maze_height {
+                    terminal_ui::print_solving_sequence(
+                        &mut screen,
+                        format!(
+                            "The maze ({}x{}) does not fit into the terminal (max. {}x{}).",
+                            maze.width, maze.height, max_maze_width, max_maze_height
+                        ),
+                    );
+                    continue;
+                }
+                // Allow resizing the maze afterwards like any other maze.
+                maze.max_width = max_maze_width - (1 - max_maze_width % 2);
+                maze.max_height = max_maze_height - (1 - max_maze_height % 2);
+                if let Some(maze_seed) = maze.seed {
+                    seed = maze_seed;
+                    rng = StdRng::seed_from_u64(seed);
+                }
+                terminal_ui::erase_draw_area(&mut screen);
+                maze_container = MazeContainer::SingleMaze(Box::new(maze));
+                terminal_ui::print_informations(
                     &mut screen,
+                    maze_container.get_size(),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+                terminal_ui::print_solving_sequence(
+                    &mut screen,
+                    format!("Maze loaded from {}.", filename),
                 );
                 maze_container.draw(
                     &mut screen,
@@ -163,55 +403,26 @@ fn main() {
                     show_grid_representation,
                 );
             }
-            Key::Up | Key::Char('k') => {
-                // Increase size.
-                if maze_container.change_size(2, 2) {
-                    terminal_ui::erase_draw_area(&mut screen);
-                    // Generate without animation.
-                    maze_container.generate(
-                        match generation_algorithm {
-                            MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                            MazeGenerationAlgorithms::RecursiveBacktracking => {
-                                &RecursiveBacktracking
-                            }
-                            MazeGenerationAlgorithms::Wilson => &Wilson,
-                        },
-                        &mut screen,
-                        false,
-                    );
-                    maze_container.draw(
-                        &mut screen,
-                        show_graph,
-                        show_background_graph,
-                        show_binary_representation,
-                        show_background_binary_representation,
-                        show_grid_representation,
-                    );
-                    // Reset the informations in the UI.
-                    terminal_ui::print_informations(
-                        &mut screen,
-                        maze_container.get_size(),
-                        generation_algorithm.to_string(),
-                        solving_algorithm.to_string(),
-                        0,
-                        animate,
-                    );
-                    terminal_ui::print_solving_sequence(&mut screen, String::new());
-                }
-            }
-            Key::Down | Key::Char('j') => {
-                // Decrease size.
-                if maze_container.change_size(-2, -2) {
+            Key::Char('k') | Key::Char('j') | Key::Up | Key::Down | Key::Right | Key::Left => {
+                // Change the size. k / j change both width and height, the arrow keys only one of them.
+                let (change_in_width, change_in_height) = match key {
+                    Key::Char('k') => (2, 2),
+                    Key::Char('j') => (-2, -2),
+                    Key::Up => (0, 2),
+                    Key::Down => (0, -2),
+                    Key::Right => (2, 0),
+                    Key::Left => (-2, 0),
+                    _ => unreachable!(),
+                };
+                if maze_container.change_size(change_in_width, change_in_height) {
                     terminal_ui::erase_draw_area(&mut screen);
                     // Generate without animation.
                     maze_container.generate(
-                        match generation_algorithm {
-                            MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                            MazeGenerationAlgorithms::RecursiveBacktracking => {
-                                &RecursiveBacktracking
-                            }
-                            MazeGenerationAlgorithms::Wilson => &Wilson,
-                        },
+                        &*Terrain::wrap(
+                            Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                            terrain,
+                        ),
+                        seed,
                         &mut screen,
                         false,
                     );
@@ -227,10 +438,13 @@ fn main() {
                     terminal_ui::print_informations(
                         &mut screen,
                         maze_container.get_size(),
-                        generation_algorithm.to_string(),
+                        &generation_algorithm.to_string(),
                         solving_algorithm.to_string(),
                         0,
                         animate,
+                        dead_end_removal,
+                        terrain,
+                        seed,
                     );
                     terminal_ui::print_solving_sequence(&mut screen, String::new());
                 }
@@ -242,14 +456,17 @@ fn main() {
                     terminal_ui::print_informations(
                         &mut screen,
                         (maze.width, maze.height),
-                        generation_algorithm.to_string(),
+                        &generation_algorithm.to_string(),
                         solving_algorithm.to_string(),
                         0,
                         animate,
+                        dead_end_removal,
+                        terrain,
+                        seed,
                     );
                     terminal_ui::print_solving_sequence(&mut screen, String::new());
                     // Make the start and end position random.
-                    maze.set_random_start_end_position();
+                    maze.set_random_start_end_position(&mut rng);
                     // Redraw.
                     maze.draw(
                         &mut screen,
@@ -268,10 +485,13 @@ fn main() {
                     terminal_ui::print_informations(
                         &mut screen,
                         (maze.width, maze.height),
-                        generation_algorithm.to_string(),
+                        &generation_algorithm.to_string(),
                         solving_algorithm.to_string(),
                         0,
                         animate,
+                        dead_end_removal,
+                        terrain,
+                        seed,
                     );
                     terminal_ui::print_solving_sequence(&mut screen, String::new());
                     // Reset the start and end position.
@@ -289,8 +509,12 @@ fn main() {
             }
             Key::Char('s') => {
                 if let MazeContainer::MultipleMazes(_) = maze_container {
-                    if solving_algorithm == MazeSolvingAlgorithms::WallFollower {
-                        terminal_ui::print_solving_sequence(&mut screen, String::from("For multiple mazes, the solving algorithm 'wall follower' is not supported. Please change the solving algorithm."));
+                    // These solvers do not always find a path.
+                    if matches!(
+                        solving_algorithm,
+                        MazeSolvingAlgorithms::WallFollower(_) | MazeSolvingAlgorithms::Pledge
+                    ) {
+                        terminal_ui::print_solving_sequence(&mut screen, format!("For multiple mazes, the solving algorithm '{}' is not supported. Please change the solving algorithm.", solving_algorithm.to_string()));
                         continue;
                     }
                 }
@@ -301,34 +525,33 @@ fn main() {
                     show_background_binary_representation,
                     show_grid_representation,
                 ) = (false, false, false, false, false);
-                let (path, number_of_inspected_cells) = maze_container.solve(
-                    match solving_algorithm {
-                        MazeSolvingAlgorithms::AStar => &AStar,
-                        MazeSolvingAlgorithms::AStarWeighted => &AStarWeighted,
-                        MazeSolvingAlgorithms::BreadthFirstSearch => &BreadthFirstSearch,
-
-                        MazeSolvingAlgorithms::DepthFirstSearch => &DepthFirstSearch,
-                        MazeSolvingAlgorithms::GreedyBestFirstSearch => &GreedyBestFirstSearch,
-                        MazeSolvingAlgorithms::WallFollower => &WallFollower,
-                    },
-                    &mut screen,
-                    animate,
-                );
+                let (path, number_of_inspected_cells) =
+                    maze_container.solve(&*solving_algorithm.to_solver(), &mut screen, animate);
                 let solving_sequence = get_solving_sequence(&path);
                 let mut solving_sequence: String = solving_sequence.iter().collect();
                 if solving_sequence.len() == 0 {
                     solving_sequence = String::from("No solving sequence available.");
                 } else {
                     solving_sequence.push_str(format!(" ({})", solving_sequence.len()).as_str());
+                    if let MazeContainer::SingleMaze(ref maze) = maze_container {
+                        if terrain {
+                            solving_sequence.push_str(
+                                format!(" cost: {}", calculate_path_cost(maze, &path)).as_str(),
+                            );
+                        }
+                    }
                 }
                 // Print the informations in the UI.
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     number_of_inspected_cells,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, solving_sequence);
             }
@@ -344,19 +567,8 @@ fn main() {
                     show_background_binary_representation,
                     show_grid_representation,
                 ) = (false, false, false, false, false);
-                let (path, number_of_inspected_cells) = maze_container.solve(
-                    match solving_algorithm {
-                        MazeSolvingAlgorithms::AStar => &AStar,
-                        MazeSolvingAlgorithms::AStarWeighted => &AStarWeighted,
-                        MazeSolvingAlgorithms::BreadthFirstSearch => &BreadthFirstSearch,
-
-                        MazeSolvingAlgorithms::DepthFirstSearch => &DepthFirstSearch,
-                        MazeSolvingAlgorithms::GreedyBestFirstSearch => &GreedyBestFirstSearch,
-                        MazeSolvingAlgorithms::WallFollower => &WallFollower,
-                    },
-                    &mut screen,
-                    false,
-                );
+                let (path, number_of_inspected_cells) =
+                    maze_container.solve(&*solving_algorithm.to_solver(), &mut screen, false);
                 maze_container.draw(&mut screen, false, false, false, false, false);
                 if let MazeContainer::SingleMaze(ref maze) = maze_container {
                     draw_path(&mut screen, &maze, path.clone(), Some(CellColorType::Path));
@@ -370,13 +582,75 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     number_of_inspected_cells,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, solving_sequence);
             }
+            Key::Char('x') => {
+                // Step through the solving algorithm. (only for single maze)
+                let MazeContainer::SingleMaze(ref mut maze) = maze_container else {
+                    continue;
+                };
+                if !maze.is_generated {
+                    continue;
+                }
+                (
+                    show_graph,
+                    show_background_graph,
+                    show_binary_representation,
+                    show_background_binary_representation,
+                    show_grid_representation,
+                ) = (false, false, false, false, false);
+                let solver = solving_algorithm.to_solver();
+                let mut step_debugger = StepDebugger::new(maze, &*solver);
+                step_debugger.redraw(&mut screen, maze);
+                // The side panel is right of the maze (or below if there is no space).
+                let (maze_pos_x, _) = calculate_maze_position(maze);
+                let panel_left = maze_pos_x + maze.width as u16 + 2;
+                loop {
+                    let score_name = solver.frontier_score_name();
+                    let lines = step_debugger.describe(score_name);
+                    if terminal_ui::print_side_panel(&mut screen, panel_left, &lines) {
+                        terminal_ui::print_solving_sequence(
+                            &mut screen,
+                            String::from("→ / space: next step, ←: previous step, Esc: quit"),
+                        );
+                    } else {
+                        terminal_ui::print_solving_sequence(
+                            &mut screen,
+                            step_debugger.summarize(score_name),
+                        );
+                    }
+                    match keys.next() {
+                        Some(Ok(Key::Right)) | Some(Ok(Key::Char(' '))) => {
+                            step_debugger.step_forward(&mut screen, maze)
+                        }
+                        Some(Ok(Key::Left)) => step_debugger.step_backward(&mut screen, maze),
+                        Some(Ok(Key::Esc)) | Some(Ok(Key::Char('q'))) | None => break,
+                        _ => {}
+                    }
+                }
+                terminal_ui::erase_draw_area(&mut screen);
+                maze.draw(&mut screen, false, false, false, false, false);
+                terminal_ui::print_informations(
+                    &mut screen,
+                    (maze.width, maze.height),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    step_debugger.number_of_inspected_cells,
+                    animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+                terminal_ui::print_solving_sequence(&mut screen, String::new());
+            }
             Key::Char('h') => {
                 // Next generation algorithm.
                 generation_algorithm = generation_algorithm.next();
@@ -384,19 +658,97 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
                 // Recreate (without animation).
                 maze_container.generate(
-                    match generation_algorithm {
-                        MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                        MazeGenerationAlgorithms::RecursiveBacktracking => &RecursiveBacktracking,
-                        MazeGenerationAlgorithms::Wilson => &Wilson,
-                    },
+                    &*Terrain::wrap(
+                        Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                        terrain,
+                    ),
+                    seed,
+                    &mut screen,
+                    false,
+                );
+                maze_container.draw(
+                    &mut screen,
+                    show_graph,
+                    show_background_graph,
+                    show_binary_representation,
+                    show_background_binary_representation,
+                    show_grid_representation,
+                );
+            }
+            Key::Char('c') => {
+                // Cycle through the fraction of dead ends that are removed (braided mazes).
+                dead_end_removal = match (dead_end_removal * 100.0).round() as usize {
+                    0 => 0.25,
+                    25 => 0.5,
+                    50 => 1.0,
+                    _ => 0.0,
+                };
+                // Print / reset the informations in the UI.
+                terminal_ui::print_informations(
+                    &mut screen,
+                    maze_container.get_size(),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    0,
+                    animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+                terminal_ui::print_solving_sequence(&mut screen, String::new());
+                // Recreate (without animation).
+                maze_container.generate(
+                    &*Terrain::wrap(
+                        Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                        terrain,
+                    ),
+                    seed,
+                    &mut screen,
+                    false,
+                );
+                maze_container.draw(
+                    &mut screen,
+                    show_graph,
+                    show_background_graph,
+                    show_binary_representation,
+                    show_background_binary_representation,
+                    show_grid_representation,
+                );
+            }
+            Key::Char('u') => {
+                // Toggle the terrain (grass, mud and water) that is expensive to walk through.
+                terrain = !terrain;
+                // Print / reset the informations in the UI.
+                terminal_ui::print_informations(
+                    &mut screen,
+                    maze_container.get_size(),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    0,
+                    animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+                terminal_ui::print_solving_sequence(&mut screen, String::new());
+                // Recreate (without animation).
+                maze_container.generate(
+                    &*Terrain::wrap(
+                        Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                        terrain,
+                    ),
+                    seed,
                     &mut screen,
                     false,
                 );
@@ -416,10 +768,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
                 // Redraw the maze but do not solve it yet (may trigger the animation).
@@ -439,10 +794,28 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
+                );
+            }
+            Key::Char('+') | Key::Char('-') => {
+                // Change the speed of the animations.
+                change_animation_speed(if key == Key::Char('+') { 1 } else { -1 });
+                terminal_ui::print_informations(
+                    &mut screen,
+                    maze_container.get_size(),
+                    &generation_algorithm.to_string(),
+                    solving_algorithm.to_string(),
+                    0,
+                    animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
             }
             Key::Char('g') => {
@@ -470,10 +843,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
             }
@@ -508,10 +884,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
             }
@@ -536,10 +915,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
             }
@@ -552,24 +934,24 @@ fn main() {
                     terminal_ui::print_informations(
                         &mut screen,
                         maze_container.get_size(),
-                        generation_algorithm.to_string(),
+                        &generation_algorithm.to_string(),
                         solving_algorithm.to_string(),
                         0,
                         animate,
+                        dead_end_removal,
+                        terrain,
+                        seed,
                     );
                     terminal_ui::print_solving_sequence(&mut screen, String::new());
                     // Generate the maze (without animation) and print it.
                     let mut maze = Maze::new(max_maze_width, max_maze_height, (1, 1));
                     maze.generate(
-                        match generation_algorithm {
-                            MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                            MazeGenerationAlgorithms::RecursiveBacktracking => {
-                                &RecursiveBacktracking
-                            }
-                            MazeGenerationAlgorithms::Wilson => &Wilson,
-                        },
-                        &mut screen,
-                        false,
+                        &*Terrain::wrap(
+                            Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                            terrain,
+                        ),
+                        seed,
+                        &mut TerminalRenderer::new(&mut screen, false),
                     );
                     maze.draw(
                         &mut screen,
@@ -579,7 +961,7 @@ fn main() {
                         show_background_binary_representation,
                         show_grid_representation,
                     );
-                    maze_container = MazeContainer::SingleMaze(maze);
+                    maze_container = MazeContainer::SingleMaze(Box::new(maze));
                 }
             }
             Key::Char('2') | Key::Char('3') | Key::Char('4') | Key::Char('5') => {
@@ -613,21 +995,22 @@ fn main() {
                         terminal_ui::print_informations(
                             &mut screen,
                             maze_container.get_size(),
-                            generation_algorithm.to_string(),
+                            &generation_algorithm.to_string(),
                             solving_algorithm.to_string(),
                             0,
                             animate,
+                            dead_end_removal,
+                            terrain,
+                            seed,
                         );
                         terminal_ui::print_solving_sequence(&mut screen, String::new());
                         // Create the mazes without animation and draw them.
                         maze_collection.generate(
-                            match generation_algorithm {
-                                MazeGenerationAlgorithms::Kruskal => &Kruskal,
-                                MazeGenerationAlgorithms::RecursiveBacktracking => {
-                                    &RecursiveBacktracking
-                                }
-                                MazeGenerationAlgorithms::Wilson => &Wilson,
-                            },
+                            &*Terrain::wrap(
+                                Braid::wrap(generation_algorithm.to_generator(), dead_end_removal),
+                                terrain,
+                            ),
+                            seed,
                             &mut screen,
                             false,
                         );
@@ -660,10 +1043,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 terminal_ui::print_solving_sequence(&mut screen, String::new());
             }
@@ -675,7 +1061,10 @@ fn main() {
                     String::from("Executing benchmark ..."),
                 );
                 let start_time = Instant::now();
-                let mut benchmark_results = BenchmarkResultCollection::new();
+                let mut benchmark_results = BenchmarkResultCollection::new(
+                    dead_end_removal,
+                    GROWING_TREE_MIXED_RATIO_DEFAULT,
+                );
                 loop {
                     let (is_running, progress) = benchmark_results.benchmark_next_chunk();
                     terminal_ui::print_solving_sequence(
@@ -702,10 +1091,13 @@ fn main() {
                 terminal_ui::print_informations(
                     &mut screen,
                     maze_container.get_size(),
-                    generation_algorithm.to_string(),
+                    &generation_algorithm.to_string(),
                     solving_algorithm.to_string(),
                     0,
                     animate,
+                    dead_end_removal,
+                    terrain,
+                    seed,
                 );
                 maze_container.draw(
                     &mut screen,
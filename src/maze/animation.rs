@@ -1,10 +1,40 @@
+use std::cell::{Cell, RefCell};
 use std::{thread, time::Duration};
 
 const ANIMATION_DELAY_SHORT_MILLISECONDS: u64 = 8;
 const ANIMATION_DELAY_MIDDLE_MILLISECONDS: u64 = 15;
 const ANIMATION_DELAY_LONG_MILLISECONDS: u64 = 50;
 const ANIMATION_DELAY_VERY_LONG_MILLISECONDS: u64 = 1000;
+// The delays are split into slices of this length to react to commands in the meantime.
+const ANIMATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+// Every step of the speed halves (or doubles) the delays.
+const ANIMATION_SPEED_MIN: i32 = -3;
+const ANIMATION_SPEED_MAX: i32 = 3;
 
+thread_local! {
+    // While recording, the animations run on a virtual clock: the delays only advance the
+    // virtual time instead of sleeping.
+    static VIRTUAL_TIME: Cell<Option<Duration>> = const { Cell::new(None) };
+    static ANIMATION_SPEED: Cell<i32> = const { Cell::new(0) };
+    static ANIMATION_PAUSED: Cell<bool> = const { Cell::new(false) };
+    // An aborted animation skips all delays until it is resumed.
+    static ANIMATION_ABORTED: Cell<bool> = const { Cell::new(false) };
+    // Where the commands for the running animation come from (e.g. the key presses).
+    static ANIMATION_COMMANDS: RefCell<Option<AnimationCommandSource>> = const { RefCell::new(None) };
+}
+
+// Returns the next command (or None if there is no new one).
+type AnimationCommandSource = Box<dyn FnMut() -> Option<AnimationCommand>>;
+
+// Changes the running animation.
+pub enum AnimationCommand {
+    Faster,
+    Slower,
+    TogglePause,
+    Abort,
+}
+
+#[derive(Clone)]
 pub enum Delay {
     NoDelay,
     Short,
@@ -26,6 +56,91 @@ impl Delay {
 }
 
 pub fn delay(delay: Delay) {
-    let duration = Duration::from_millis(delay.to_milliseconds());
-    thread::sleep(duration);
+    let speed = ANIMATION_SPEED.with(|speed| speed.get());
+    let mut remaining = Duration::from_millis(delay.to_milliseconds()).mul_f64(2f64.powi(-speed));
+    loop {
+        poll_animation_commands();
+        if ANIMATION_ABORTED.with(|aborted| aborted.get()) {
+            return;
+        }
+        if ANIMATION_PAUSED.with(|paused| paused.get()) {
+            thread::sleep(ANIMATION_POLL_INTERVAL);
+            continue;
+        }
+        if remaining.is_zero() {
+            return;
+        }
+        let duration = remaining.min(ANIMATION_POLL_INTERVAL);
+        VIRTUAL_TIME.with(|time| match time.get() {
+            Some(virtual_time) => time.set(Some(virtual_time + duration)),
+            None => thread::sleep(duration),
+        });
+        remaining -= duration;
+    }
+}
+
+// The commands are polled during every delay, so the animations can be controlled while they
+// are running.
+pub fn set_animation_commands(commands: AnimationCommandSource) {
+    ANIMATION_COMMANDS.with(|animation_commands| *animation_commands.borrow_mut() = Some(commands));
+}
+
+fn poll_animation_commands() {
+    let commands: Vec<AnimationCommand> = ANIMATION_COMMANDS.with(|animation_commands| {
+        match animation_commands.borrow_mut().as_mut() {
+            Some(next_command) => std::iter::from_fn(next_command).collect(),
+            None => Vec::new(),
+        }
+    });
+    for command in commands {
+        match command {
+            AnimationCommand::Faster => change_animation_speed(1),
+            AnimationCommand::Slower => change_animation_speed(-1),
+            AnimationCommand::TogglePause => {
+                ANIMATION_PAUSED.with(|paused| paused.set(!paused.get()))
+            }
+            AnimationCommand::Abort => {
+                ANIMATION_PAUSED.with(|paused| paused.set(false));
+                ANIMATION_ABORTED.with(|aborted| aborted.set(true));
+            }
+        }
+    }
+}
+
+pub fn change_animation_speed(change: i32) {
+    ANIMATION_SPEED.with(|speed| {
+        speed.set((speed.get() + change).clamp(ANIMATION_SPEED_MIN, ANIMATION_SPEED_MAX))
+    });
+}
+
+// The factor the animations are sped up by as text (e.g. "x2" or "x1/2").
+pub fn animation_speed_to_string() -> String {
+    let speed = ANIMATION_SPEED.with(|speed| speed.get());
+    if speed >= 0 {
+        format!("x{}", 1 << speed)
+    } else {
+        format!("x1/{}", 1 << -speed)
+    }
+}
+
+pub fn is_animation_aborted() -> bool {
+    ANIMATION_ABORTED.with(|aborted| aborted.get())
+}
+
+// Allows the next animations to run again after an abort.
+pub fn resume_animations() {
+    ANIMATION_ABORTED.with(|aborted| aborted.set(false));
+}
+
+pub fn start_virtual_clock() {
+    VIRTUAL_TIME.with(|time| time.set(Some(Duration::ZERO)));
+}
+
+pub fn stop_virtual_clock() {
+    VIRTUAL_TIME.with(|time| time.set(None));
+}
+
+// The time that passed since the virtual clock was started (zero if it is not running).
+pub fn virtual_time() -> Duration {
+    VIRTUAL_TIME.with(|time| time.get()).unwrap_or_default()
 }
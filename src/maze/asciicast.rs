@@ -0,0 +1,106 @@
+use crate::maze::animation::virtual_time;
+use std::io::{Result, Write};
+use std::time::{Duration, Instant};
+
+// Output written within this time is merged into one event.
+const ASCIICAST_EVENT_MERGE_DURATION: Duration = Duration::from_millis(1);
+
+// Passes everything written to the screen through and records it with timestamps as asciicast
+// (asciinema v2). The delays of the animations only advance the virtual clock, so the virtual
+// clock needs to be started: the recording then shows them while they do not slow down the
+// session. Without the real time, only the virtual clock is used (reproducible recordings).
+pub struct AsciicastRecorder<W: Write> {
+    screen: W,
+    output: Box<dyn Write>,
+    time_start: Option<Instant>,
+    // The output that is not written as event yet and the time it was written.
+    pending: Vec<u8>,
+    time_pending: Duration,
+}
+
+impl<W: Write> AsciicastRecorder<W> {
+    pub fn new(
+        screen: W,
+        mut output: Box<dyn Write>,
+        width: usize,
+        height: usize,
+        use_real_time: bool,
+    ) -> Result<Self> {
+        writeln!(
+            output,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+            width, height
+        )?;
+        Ok(AsciicastRecorder {
+            screen,
+            output,
+            time_start: if use_real_time {
+                Some(Instant::now())
+            } else {
+                None
+            },
+            pending: Vec::new(),
+            time_pending: Duration::ZERO,
+        })
+    }
+
+    fn time(&self) -> Duration {
+        virtual_time()
+            + self
+                .time_start
+                .map_or(Duration::ZERO, |time_start| time_start.elapsed())
+    }
+
+    fn write_event(&mut self) -> Result<()> {
+        // An incomplete character at the end stays pending for the next event.
+        let length = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if length == 0 {
+            return Ok(());
+        }
+        let data: Vec<u8> = self.pending.drain(..length).collect();
+        writeln!(
+            self.output,
+            "{}",
+            serde_json::json!([
+                self.time_pending.as_secs_f64(),
+                "o",
+                String::from_utf8_lossy(&data)
+            ])
+        )
+    }
+
+    // Writes the remaining output. This is also done when the recorder is dropped.
+    pub fn finish(&mut self) -> Result<()> {
+        self.write_event()?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Write for AsciicastRecorder<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let time = self.time();
+        if !self.pending.is_empty() && time >= self.time_pending + ASCIICAST_EVENT_MERGE_DURATION {
+            self.write_event()?;
+        }
+        if self.pending.is_empty() {
+            self.time_pending = time;
+        }
+        let length = self.screen.write(buf)?;
+        self.pending.extend_from_slice(&buf[..length]);
+        Ok(length)
+    }
+    fn flush(&mut self) -> Result<()> {
+        self.write_event()?;
+        self.screen.flush()
+    }
+}
+
+impl<W: Write> Drop for AsciicastRecorder<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
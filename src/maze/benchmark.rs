@@ -1,7 +1,10 @@
+use crate::maze::event::NullEventHandler;
 use crate::maze::generator::*;
 use crate::maze::maze::*;
 use crate::maze::path::calculate_manhattan_distance;
 use crate::maze::solver::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Result, Write};
@@ -28,23 +31,31 @@ impl Write for NullWriter {
 
 pub struct BenchmarkResult {
     pub maze_id: usize,
+    pub seed: u64,
     pub generation_algorithm: String,
     pub manhattan_distance: usize,
     pub path_length: usize,
     pub inspected_cells_per_solving_algorithm: HashMap<String, usize>,
+    // Only the graph solvers, they inspect nodes and cover the cells of their corridors.
+    pub covered_cells_per_solving_algorithm: HashMap<String, usize>,
 }
 
 pub struct BenchmarkResultCollection {
     maze_width: usize,
     maze_height: usize,
+    dead_end_removal: f64,
+    // The probability of choosing the newest cell in the mixed growing tree.
+    mixed_ratio: f64,
     pub results: Vec<BenchmarkResult>,
 }
 
 impl BenchmarkResultCollection {
-    pub fn new() -> Self {
+    pub fn new(dead_end_removal: f64, mixed_ratio: f64) -> Self {
         Self {
             maze_width: BENCHMARK_MAZE_WIDTH,
             maze_height: BENCHMARK_MAZE_HEIGHT,
+            dead_end_removal,
+            mixed_ratio,
             results: Vec::new(),
         }
     }
@@ -64,14 +75,55 @@ impl BenchmarkResultCollection {
     }
 
     pub fn benchmark_next_chunk(&mut self) -> (bool, usize) {
-        let generation_algorithms: Arc<Vec<&dyn MazeGenerator>> =
-            Arc::new(vec![&Kruskal, &RecursiveBacktracking, &Wilson]);
+        let generation_algorithms: Vec<Box<dyn MazeGenerator>> = vec![
+            Box::new(AldousBroder),
+            Box::new(BinaryTree {
+                bias: DiagonalBias::NorthEast,
+            }),
+            Box::new(Eller),
+            Box::new(GrowingTree {
+                strategy: GrowingTreeStrategy::Newest,
+            }),
+            Box::new(GrowingTree {
+                strategy: GrowingTreeStrategy::Mixed(self.mixed_ratio),
+            }),
+            Box::new(HuntAndKill),
+            Box::new(Kruskal),
+            Box::new(Prim),
+            Box::new(RecursiveBacktracking),
+            Box::new(RecursiveDivision),
+            Box::new(Sidewinder {
+                bias: DiagonalBias::NorthEast,
+            }),
+            Box::new(Wilson),
+        ];
+        // Turn the perfect mazes into braided mazes if requested.
+        let generation_algorithms: Arc<Vec<Box<dyn MazeGenerator>>> = Arc::new(
+            generation_algorithms
+                .into_iter()
+                .map(|generator| Braid::wrap(generator, self.dead_end_removal))
+                .collect(),
+        );
         let solving_algorithms: Arc<Vec<&dyn MazeSolver>> = Arc::new(vec![
             &BreadthFirstSearch,
+            &BidirectionalBreadthFirstSearch,
             &DepthFirstSearch,
+            &DeadEndFilling,
+            &CulDeSacFilling,
             &AStar,
+            &BidirectionalAStar,
             &AStarWeighted,
+            &Dijkstra,
             &GreedyBestFirstSearch,
+            &GraphBreadthFirstSearch,
+            &GraphDijkstra,
+            &GraphAStar,
+            &WallFollower {
+                side: WallSide::Right,
+            },
+            &WallFollower {
+                side: WallSide::Left,
+            },
         ]);
 
         // Are we done?
@@ -89,6 +141,7 @@ impl BenchmarkResultCollection {
             );
         }
 
+        let is_braided = self.dead_end_removal > 0.0;
         let maze_id_start = self.calculate_current_number_of_mazes();
         let chunk_results: Arc<Mutex<Vec<BenchmarkResult>>> = Arc::new(Mutex::new(Vec::new()));
         let mut thread_handles = Vec::new();
@@ -97,7 +150,6 @@ impl BenchmarkResultCollection {
             let generation_algorithms = generation_algorithms.clone();
             let solving_algorithms = solving_algorithms.clone();
             let thread_handle = thread::spawn(move || {
-                let mut screen = NullWriter;
                 let mut thread_results: Vec<BenchmarkResult> = Vec::new();
                 let mut maze_id =
                     maze_id_start + idx_thread * BENCHMARK_CHUNK_SIZE * generation_algorithms.len();
@@ -112,29 +164,55 @@ impl BenchmarkResultCollection {
                         if maze.change_size(BENCHMARK_MAZE_WIDTH, BENCHMARK_MAZE_HEIGHT) == false {
                             panic!();
                         };
-                        maze.generate(*generation_algorithm, &mut screen, false);
+                        // The maze id is used as seed, so every maze of the benchmark
+                        // can be reproduced.
+                        let seed = maze_id as u64;
+                        let mut rng = StdRng::seed_from_u64(seed);
+                        maze.generate(&**generation_algorithm, seed, &mut NullEventHandler);
                         for i in 0..=BENCHMARK_NUMBER_OF_RANDOM_POSITIONS_PER_MAZE {
                             if i > 0 {
-                                maze.set_random_start_end_position();
+                                maze.set_random_start_end_position(&mut rng);
                             }
                             let mut inspected_cells_per_solving_algorithm: HashMap<String, usize> =
                                 HashMap::new();
+                            let mut covered_cells_per_solving_algorithm: HashMap<String, usize> =
+                                HashMap::new();
+                            // The breadth-first search comes first, its path is the shortest one.
                             let mut path_length = 0;
                             for solving_algorithm in solving_algorithms.iter() {
                                 // Solve the maze and count the number of inspected cells.
                                 let (path, number_of_inspected_cells) =
-                                    maze.solve(*solving_algorithm, &mut screen, false);
-                                if path_length != 0 && path_length != path.len() {
-                                    panic!()
+                                    maze.solve(*solving_algorithm, &mut NullEventHandler);
+                                // In a perfect maze, there is only one path. In a braided maze,
+                                // only some solving algorithms guarantee to find the shortest one.
+                                if path_length == 0 {
+                                    path_length = path.len();
+                                } else if (!is_braided || solving_algorithm.finds_shortest_path())
+                                    && path.len() != path_length
+                                {
+                                    panic!(
+                                        "{} found a path of length {}, expected {} (seed: {}).",
+                                        solving_algorithm.to_string(),
+                                        path.len(),
+                                        path_length,
+                                        seed
+                                    )
                                 }
-                                path_length = path.len();
                                 inspected_cells_per_solving_algorithm.insert(
                                     solving_algorithm.to_string(),
                                     number_of_inspected_cells,
                                 );
+                                if let Some(number_of_covered_cells) = maze.number_of_covered_cells
+                                {
+                                    covered_cells_per_solving_algorithm.insert(
+                                        solving_algorithm.to_string(),
+                                        number_of_covered_cells,
+                                    );
+                                }
                             }
                             thread_results.push(BenchmarkResult {
                                 maze_id,
+                                seed,
                                 generation_algorithm: generation_algorithm.to_string(),
                                 manhattan_distance: calculate_manhattan_distance(
                                     maze.pos_start,
@@ -142,6 +220,7 @@ impl BenchmarkResultCollection {
                                 ),
                                 path_length,
                                 inspected_cells_per_solving_algorithm,
+                                covered_cells_per_solving_algorithm,
                             });
                         }
                         maze_id += 1;
@@ -168,15 +247,20 @@ impl BenchmarkResultCollection {
 
     pub fn to_csv(&self) -> String {
         let filename = format!(
-            "benchmark_analysis/maze_benchmark_size_{}x{}_{}_mazes_{}_random_positions.csv",
+            "benchmark_analysis/maze_benchmark_size_{}x{}_{}_mazes_{}_random_positions{}.csv",
             self.maze_width,
             self.maze_height,
             BENCHMARK_NUMBER_OF_MAZES_PER_GENERATION_ALGORITHM,
-            BENCHMARK_NUMBER_OF_RANDOM_POSITIONS_PER_MAZE
+            BENCHMARK_NUMBER_OF_RANDOM_POSITIONS_PER_MAZE,
+            if self.dead_end_removal > 0.0 {
+                format!("_braid_{}", (self.dead_end_removal * 100.0).round())
+            } else {
+                String::new()
+            }
         );
         let mut file = File::create(filename.clone()).unwrap();
         // Header.
-        file.write_all(b"maze_id;generation_algorithm;manhattan_distance;path_length")
+        file.write_all(b"maze_id;seed;generation_algorithm;manhattan_distance;path_length")
             .unwrap();
         let solving_algorithms: Vec<String> = self.results[0]
             .inspected_cells_per_solving_algorithm
@@ -187,13 +271,28 @@ impl BenchmarkResultCollection {
             file.write_all(format!(";{}", solving_algorithm).into_bytes().as_slice())
                 .unwrap();
         }
+        // The graph solvers count nodes above, the cells they cover follow.
+        let graph_solving_algorithms: Vec<String> = self.results[0]
+            .covered_cells_per_solving_algorithm
+            .keys()
+            .cloned()
+            .collect();
+        for solving_algorithm in graph_solving_algorithms.iter() {
+            file.write_all(
+                format!(";{} (covered cells)", solving_algorithm)
+                    .into_bytes()
+                    .as_slice(),
+            )
+            .unwrap();
+        }
         file.write_all(b"\n").unwrap();
         // Write all measurements into the csv file.
         for result in self.results.iter() {
             file.write_all(
                 format!(
-                    "{};{};{};{}",
+                    "{};{};{};{};{}",
                     result.maze_id,
+                    result.seed,
                     result.generation_algorithm,
                     result.manhattan_distance,
                     result.path_length
@@ -216,6 +315,20 @@ impl BenchmarkResultCollection {
                 )
                 .unwrap();
             }
+            for solving_algorithm in graph_solving_algorithms.iter() {
+                file.write_all(
+                    format!(
+                        ";{}",
+                        result
+                            .covered_cells_per_solving_algorithm
+                            .get(solving_algorithm)
+                            .unwrap()
+                    )
+                    .into_bytes()
+                    .as_slice(),
+                )
+                .unwrap();
+            }
             file.write_all(b"\n").unwrap();
         }
         // Return the filename.
@@ -1,3 +1,5 @@
+use crate::maze::animation::{delay, is_animation_aborted};
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::maze::*;
 use crate::maze::path::*;
 use std::io::Write;
@@ -30,32 +32,37 @@ const CHAR_MAZE_BLOCKED: char = '1';
 
 const MULTIPLE_MAZES_DRAW_DISTANCE: usize = 2;
 
+// The costs of the fields are shown on a color scale from cheap to expensive. Fields with at
+// least this cost get the color of the most expensive ones.
+const COST_COLOR_SCALE_MAX: usize = 8;
+const COST_COLOR_CHEAP: (u8, u8, u8) = (170, 215, 120);
+const COST_COLOR_EXPENSIVE: (u8, u8, u8) = (35, 75, 160);
+
 #[derive(Clone, Copy)]
 pub enum CellColorType {
     InspectedCell,
+    // Inspected by the search that starts at the end position (bidirectional solvers).
+    InspectedCellFromEnd,
     CurrentCell,
     Path,
 }
 
 impl CellColorType {
     // https://coolors.co/palettes/trending
-    pub fn to_termion_color(&self) -> String {
+    pub fn to_rgb(self) -> (u8, u8, u8) {
         match self {
-            Self::InspectedCell => {
-                let (r, g, b) = Self::termion_rgb_from_string(String::from("ffd166"));
-                format!("{}", termion::color::Bg(termion::color::Rgb(r, g, b)))
-            }
-            Self::CurrentCell => {
-                let (r, g, b) = Self::termion_rgb_from_string(String::from("ef476f"));
-                format!("{}", termion::color::Bg(termion::color::Rgb(r, g, b)))
-            }
-            Self::Path => {
-                let (r, g, b) = Self::termion_rgb_from_string(String::from("06d6a0"));
-                format!("{}", termion::color::Bg(termion::color::Rgb(r, g, b)))
-            }
+            Self::InspectedCell => Self::termion_rgb_from_string(String::from("ffd166")),
+            Self::InspectedCellFromEnd => Self::termion_rgb_from_string(String::from("118ab2")),
+            Self::CurrentCell => Self::termion_rgb_from_string(String::from("ef476f")),
+            Self::Path => Self::termion_rgb_from_string(String::from("06d6a0")),
         }
     }
 
+    pub fn to_termion_color(&self) -> String {
+        let (r, g, b) = self.to_rgb();
+        format!("{}", termion::color::Bg(termion::color::Rgb(r, g, b)))
+    }
+
     pub fn termion_rgb_from_string(color_string: String) -> (u8, u8, u8) {
         if color_string.len() != 6 {
             panic!()
@@ -67,13 +74,29 @@ impl CellColorType {
     }
 }
 
-fn calculate_maze_position(maze: &Maze) -> (u16, u16) {
-    let (terminal_width, terminal_height) = termion::terminal_size().unwrap();
-    let y = (terminal_height - maze.height as u16) / 2 + 1;
+// The color of a field with the given cost (the cost doubles from step to step of the scale).
+pub fn cost_to_rgb(cost: usize) -> (u8, u8, u8) {
+    let scale = ((cost.max(MAZE_COST_DEFAULT) as f64).log2()
+        / (COST_COLOR_SCALE_MAX as f64).log2())
+    .min(1.0);
+    let interpolate =
+        |cheap: u8, expensive: u8| (cheap as f64 + (expensive as f64 - cheap as f64) * scale) as u8;
+    (
+        interpolate(COST_COLOR_CHEAP.0, COST_COLOR_EXPENSIVE.0),
+        interpolate(COST_COLOR_CHEAP.1, COST_COLOR_EXPENSIVE.1),
+        interpolate(COST_COLOR_CHEAP.2, COST_COLOR_EXPENSIVE.2),
+    )
+}
+
+pub fn calculate_maze_position(maze: &Maze) -> (u16, u16) {
+    // Without a terminal (e.g. when running headless), the maze is placed in the top left corner.
+    let (terminal_width, terminal_height) =
+        termion::terminal_size().unwrap_or((maze.width as u16, maze.height as u16));
+    let y = terminal_height.saturating_sub(maze.height as u16) / 2 + 1;
     let x = if maze.collection_position.1 % 2 == 1 {
         // Odd number of mazes in this collection.
         let idx_middle_maze = (maze.collection_position.1 + 1) / 2;
-        let pos_center = (terminal_width - maze.width as u16) / 2 + 1;
+        let pos_center = terminal_width.saturating_sub(maze.width as u16) / 2 + 1;
         let idx_difference: isize =
             -(idx_middle_maze as isize - maze.collection_position.0 as isize);
         (pos_center as isize
@@ -120,14 +143,25 @@ pub fn draw_maze(screen: &mut dyn Write, maze: &Maze) {
                 .enumerate()
                 .map(|(col, &is_accessible)| {
                     match is_accessible {
-                        MAZE_VALUE_BLOCKED => SYMBOL_MAZE_FIELD_BLOCKED,
+                        MAZE_VALUE_BLOCKED => SYMBOL_MAZE_FIELD_BLOCKED.to_string(),
                         MAZE_VALUE_ACCESSIBLE => {
                             if (col, row) == maze.pos_start {
-                                SYMBOL_MAZE_POS_START
+                                SYMBOL_MAZE_POS_START.to_string()
                             } else if (col, row) == maze.pos_end {
-                                SYMBOL_MAZE_POS_END
+                                SYMBOL_MAZE_POS_END.to_string()
+                            } else if maze.costs[row][col] > MAZE_COST_DEFAULT {
+                                // Fields with terrain.
+                                let color = cost_to_rgb(maze.costs[row][col]);
+                                format!(
+                                    "{}{}{}",
+                                    termion::color::Bg(termion::color::Rgb(
+                                        color.0, color.1, color.2
+                                    )),
+                                    SYMBOL_MAZE_FIELD_ACCESSIBLE,
+                                    termion::color::Bg(termion::color::Reset)
+                                )
                             } else {
-                                SYMBOL_MAZE_FIELD_ACCESSIBLE
+                                SYMBOL_MAZE_FIELD_ACCESSIBLE.to_string()
                             }
                         }
                     }
@@ -392,18 +426,22 @@ pub fn draw_path(
                 ) {
                     // single position.
                     (0, 0, 0, 0) => SYMBOL_MAZE_PATH_SINGLE_POSITION,
-                    // dead end to the right.
+                    // dead end to the right (a walked route also turns around there).
                     (-1, 0, 0, 0) => SYMBOL_MAZE_PATH_DEAD_END_RIGHT,
                     (0, 0, -1, 0) => SYMBOL_MAZE_PATH_DEAD_END_RIGHT,
+                    (-1, 0, -1, 0) => SYMBOL_MAZE_PATH_DEAD_END_RIGHT,
                     // dead end to the left.
                     (1, 0, 0, 0) => SYMBOL_MAZE_PATH_DEAD_END_LEFT,
                     (0, 0, 1, 0) => SYMBOL_MAZE_PATH_DEAD_END_LEFT,
+                    (1, 0, 1, 0) => SYMBOL_MAZE_PATH_DEAD_END_LEFT,
                     // dead end to the top.
                     (0, 1, 0, 0) => SYMBOL_MAZE_PATH_DEAD_END_TOP,
                     (0, 0, 0, 1) => SYMBOL_MAZE_PATH_DEAD_END_TOP,
+                    (0, 1, 0, 1) => SYMBOL_MAZE_PATH_DEAD_END_TOP,
                     // dead end to the bottom.
                     (0, -1, 0, 0) => SYMBOL_MAZE_PATH_DEAD_END_BOTTOM,
                     (0, 0, 0, -1) => SYMBOL_MAZE_PATH_DEAD_END_BOTTOM,
+                    (0, -1, 0, -1) => SYMBOL_MAZE_PATH_DEAD_END_BOTTOM,
                     // horizontal.
                     (-1, 0, 1, 0) => SYMBOL_MAZE_PATH_HORIZONTAL,
                     (1, 0, -1, 0) => SYMBOL_MAZE_PATH_HORIZONTAL,
@@ -441,6 +479,101 @@ pub fn draw_path(
     screen.flush().unwrap();
 }
 
+// Draws the events of the generation and solving algorithms to the terminal. The steps of the
+// generation are only drawn if animated, the inspected cells and the path always.
+pub struct TerminalRenderer<'a> {
+    screen: &'a mut dyn Write,
+    animate: bool,
+    // The cell the solver is currently at and the highlight it keeps afterwards.
+    pos_current: Option<((usize, usize), CellColorType)>,
+}
+
+impl<'a> TerminalRenderer<'a> {
+    pub fn new(screen: &'a mut dyn Write, animate: bool) -> Self {
+        TerminalRenderer {
+            screen,
+            animate,
+            pos_current: None,
+        }
+    }
+}
+
+impl MazeEventHandler for TerminalRenderer<'_> {
+    fn handle(&mut self, maze: &Maze, event: MazeEvent) {
+        let animate = self.animate();
+        match event {
+            MazeEvent::MazeChanged => {
+                self.pos_current = None;
+                draw_maze(self.screen, maze);
+            }
+            MazeEvent::CellCarved { pos, highlight } if animate => {
+                draw_character(
+                    self.screen,
+                    maze,
+                    pos,
+                    SYMBOL_MAZE_FIELD_ACCESSIBLE,
+                    highlight,
+                );
+            }
+            MazeEvent::WallAdded { pos } if animate => {
+                draw_character(self.screen, maze, pos, SYMBOL_MAZE_FIELD_BLOCKED, None);
+            }
+            MazeEvent::CellsColored { positions, color } if animate => {
+                highlight_cells_by_rgb_color(
+                    self.screen,
+                    maze,
+                    positions.iter().map(|&(x, y)| (y, x)).collect(),
+                    color,
+                );
+            }
+            MazeEvent::CellInspected {
+                pos,
+                direction,
+                highlight,
+            } => {
+                // The previous cell is inspected now, the new one is the current cell.
+                if let Some((pos_prev, highlight_prev)) = self.pos_current.replace((pos, highlight))
+                {
+                    highlight_cell(self.screen, maze, pos_prev, highlight_prev);
+                }
+                match direction {
+                    Some(direction) => draw_character(
+                        self.screen,
+                        maze,
+                        pos,
+                        direction.to_symbol(),
+                        Some(CellColorType::CurrentCell),
+                    ),
+                    None => highlight_cell(self.screen, maze, pos, CellColorType::CurrentCell),
+                }
+            }
+            MazeEvent::CellMarked { pos, symbol } => {
+                draw_character(
+                    self.screen,
+                    maze,
+                    pos,
+                    symbol,
+                    Some(CellColorType::InspectedCell),
+                );
+            }
+            MazeEvent::RouteWalked { route } => {
+                draw_path(self.screen, maze, route, Some(CellColorType::InspectedCell));
+            }
+            MazeEvent::PathFound { path } => {
+                draw_path(self.screen, maze, path, Some(CellColorType::Path));
+            }
+            MazeEvent::Pause(duration) if animate => delay(duration),
+            // The frontier is not shown in the terminal.
+            _ => {}
+        }
+    }
+
+    fn animate(&self) -> bool {
+        // After an abort, the algorithm finishes without animation.
+        self.animate && !is_animation_aborted()
+    }
+}
+
 pub fn get_unique_colors(n: usize) -> Vec<(u8, u8, u8)> {
     // Returns n unique colors or repeats the unique colors if n is too large.
     // There is an algorithm to implement this:
@@ -0,0 +1,77 @@
+use crate::maze::animation::Delay;
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::maze::Maze;
+
+// The steps of the generation and solving algorithms. The algorithms do not draw anything
+// themselves, they only emit these events. What happens with them (drawing them to the terminal,
+// recording them or nothing at all) is up to the handler.
+#[derive(Clone)]
+pub enum MazeEvent {
+    // The whole maze changed (e.g. it was reset before the generation) and needs to be redrawn.
+    MazeChanged,
+    // A field became accessible. It can be highlighted (e.g. if it is part of a frontier).
+    CellCarved {
+        pos: (usize, usize),
+        highlight: Option<CellColorType>,
+    },
+    // A field became blocked.
+    WallAdded {
+        pos: (usize, usize),
+    },
+    // Fields that belong together (e.g. the trees of Kruskal's algorithm) get the same color.
+    CellsColored {
+        positions: Vec<(usize, usize)>,
+        color: (u8, u8, u8),
+    },
+    // The solver inspects this cell now (optionally walking in the given direction). Once the
+    // solver moved on, the cell keeps the highlight (e.g. to tell the two searches of a
+    // bidirectional solver apart).
+    CellInspected {
+        pos: (usize, usize),
+        direction: Option<AbsoluteDirection>,
+        highlight: CellColorType,
+    },
+    // The solver leaves a mark in the cell (e.g. how often Trémaux's algorithm walked through
+    // it), the mark stays visible after the solver moved on.
+    CellMarked {
+        pos: (usize, usize),
+        symbol: char,
+    },
+    // The cells the solver will inspect next together with their scores (e.g. f = g + h
+    // for A*, the length of the path so far for the breadth first search), in the order the
+    // solver takes them.
+    FrontierChanged {
+        frontier: Vec<((usize, usize), usize)>,
+    },
+    // The route the solver walked from the start to the end position, including the dead ends
+    // it walked into and out again (e.g. the wall follower). The path follows with PathFound.
+    RouteWalked {
+        route: Vec<(usize, usize)>,
+    },
+    // The solver found the path from the start to the end position.
+    PathFound {
+        path: Vec<(usize, usize)>,
+    },
+    // The end of a step, animations pause here.
+    Pause(Delay),
+}
+
+pub trait MazeEventHandler {
+    fn handle(&mut self, maze: &Maze, event: MazeEvent);
+
+    // Whether the steps of the algorithms are shown one by one. Events that only matter for
+    // the animation and that are expensive to create (e.g. the frontier) are skipped otherwise.
+    fn animate(&self) -> bool;
+}
+
+// Ignores all events (e.g. for the benchmark).
+pub struct NullEventHandler;
+
+impl MazeEventHandler for NullEventHandler {
+    fn handle(&mut self, _maze: &Maze, _event: MazeEvent) {}
+
+    fn animate(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,113 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+pub struct AldousBroder;
+
+impl MazeGenerator for AldousBroder {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Choose a random cell as the current cell and mark it as visited.
+        2. Choose a random neighbor of the current cell and walk to it. If the neighbor
+            has not been visited yet, remove the wall between the two cells and mark it as visited.
+        3. Repeat step 2 until all cells have been visited.
+        */
+
+        let number_of_cells = ((maze.width - 1) / 2) * ((maze.height - 1) / 2);
+
+        // Choose a random starting cell.
+        let mut current_cell = (
+            2 * rng.gen_range(0..(maze.width - 1) / 2) + 1,
+            2 * rng.gen_range(0..(maze.height - 1) / 2) + 1,
+        );
+        maze.data[current_cell.1][current_cell.0] = MAZE_VALUE_ACCESSIBLE;
+        let mut number_of_visited_cells = 1;
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: current_cell,
+                highlight: Some(CellColorType::Path),
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+
+        while number_of_visited_cells < number_of_cells {
+            // Determine the possible directions you can choose from.
+            let mut possible_directions: Vec<AbsoluteDirection> = Vec::new();
+            // Look to the left.
+            if current_cell.0 > 1 {
+                possible_directions.push(AbsoluteDirection::Left);
+            }
+            // Look to the right.
+            if current_cell.0 < maze.width - 2 {
+                possible_directions.push(AbsoluteDirection::Right);
+            }
+            // Look to the top.
+            if current_cell.1 > 1 {
+                possible_directions.push(AbsoluteDirection::Up);
+            }
+            // Look to the bottom.
+            if current_cell.1 < maze.height - 2 {
+                possible_directions.push(AbsoluteDirection::Down);
+            }
+            // Choose a direction to walk to.
+            let direction = *possible_directions.choose(rng).unwrap();
+            let wall = direction.apply(current_cell);
+            let next_cell = direction.apply(wall);
+            // Carve a passage if the next cell was not visited yet.
+            let is_new_cell = maze.is_blocked(next_cell);
+            if is_new_cell {
+                maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+                maze.data[next_cell.1][next_cell.0] = MAZE_VALUE_ACCESSIBLE;
+                number_of_visited_cells += 1;
+            }
+            // Newly carved cells are highlighted differently from the already carved ones.
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: current_cell,
+                    highlight: None,
+                },
+            );
+            if is_new_cell {
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: wall,
+                        highlight: None,
+                    },
+                );
+            }
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: next_cell,
+                    highlight: Some(if is_new_cell {
+                        CellColorType::Path
+                    } else {
+                        CellColorType::InspectedCell
+                    }),
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+            current_cell = next_cell;
+        }
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: current_cell,
+                highlight: None,
+            },
+        );
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Aldous-Broder")
+    }
+}
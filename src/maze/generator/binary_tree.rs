@@ -0,0 +1,115 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::fmt;
+
+// The corner the passages of a biased maze lead to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagonalBias {
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl DiagonalBias {
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::NorthEast => Some(Self::NorthWest),
+            Self::NorthWest => Some(Self::SouthEast),
+            Self::SouthEast => Some(Self::SouthWest),
+            Self::SouthWest => None,
+        }
+    }
+
+    pub fn vertical_direction(&self) -> AbsoluteDirection {
+        match self {
+            Self::NorthEast | Self::NorthWest => AbsoluteDirection::Up,
+            Self::SouthEast | Self::SouthWest => AbsoluteDirection::Down,
+        }
+    }
+
+    pub fn horizontal_direction(&self) -> AbsoluteDirection {
+        match self {
+            Self::NorthEast | Self::SouthEast => AbsoluteDirection::Right,
+            Self::NorthWest | Self::SouthWest => AbsoluteDirection::Left,
+        }
+    }
+}
+
+impl fmt::Display for DiagonalBias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NorthEast => write!(f, "NE"),
+            Self::NorthWest => write!(f, "NW"),
+            Self::SouthEast => write!(f, "SE"),
+            Self::SouthWest => write!(f, "SW"),
+        }
+    }
+}
+
+// Checks if there is another cell (not only the border) in the given direction.
+pub fn has_neighbor(maze: &Maze, cell: (usize, usize), direction: AbsoluteDirection) -> bool {
+    match direction {
+        AbsoluteDirection::Left => cell.0 > 1,
+        AbsoluteDirection::Right => cell.0 < maze.width - 2,
+        AbsoluteDirection::Up => cell.1 > 1,
+        AbsoluteDirection::Down => cell.1 < maze.height - 2,
+    }
+}
+
+pub struct BinaryTree {
+    pub bias: DiagonalBias,
+}
+
+impl MazeGenerator for BinaryTree {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. For every cell, randomly remove either the wall in the vertical or in the horizontal
+            direction of the bias.
+        2. If the cell is at the border, there is only one (or no) direction left to choose from.
+        */
+
+        for row in (1..maze.height - 1).step_by(2) {
+            for col in (1..maze.width - 1).step_by(2) {
+                let cell = (col, row);
+                maze.data[cell.1][cell.0] = MAZE_VALUE_ACCESSIBLE;
+                let possible_directions: Vec<AbsoluteDirection> = [
+                    self.bias.vertical_direction(),
+                    self.bias.horizontal_direction(),
+                ]
+                .into_iter()
+                .filter(|&direction| has_neighbor(maze, cell, direction))
+                .collect();
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: cell,
+                        highlight: None,
+                    },
+                );
+                if let Some(direction) = possible_directions.choose(rng) {
+                    let wall = direction.apply(cell);
+                    maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: wall,
+                            highlight: None,
+                        },
+                    );
+                }
+                events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+            }
+        }
+    }
+
+    fn to_string(&self) -> String {
+        format!("binary tree ({})", self.bias)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+const DIRECTIONS: [AbsoluteDirection; 4] = [
+    AbsoluteDirection::Left,
+    AbsoluteDirection::Right,
+    AbsoluteDirection::Up,
+    AbsoluteDirection::Down,
+];
+
+// Wraps another generator and turns the generated perfect maze into a braided maze
+// (a maze with loops) by removing the given fraction of dead ends.
+pub struct Braid {
+    pub generator: Box<dyn MazeGenerator>,
+    pub dead_end_removal: f64,
+}
+
+impl Braid {
+    // Only wraps the generator if there are dead ends to remove.
+    pub fn wrap(
+        generator: Box<dyn MazeGenerator>,
+        dead_end_removal: f64,
+    ) -> Box<dyn MazeGenerator> {
+        if dead_end_removal > 0.0 {
+            Box::new(Braid {
+                generator,
+                dead_end_removal,
+            })
+        } else {
+            generator
+        }
+    }
+
+    fn is_dead_end(maze: &Maze, cell: (usize, usize)) -> bool {
+        maze.is_accessible(cell)
+            && DIRECTIONS
+                .iter()
+                .filter(|direction| maze.is_blocked(direction.apply(cell)))
+                .count()
+                == 3
+    }
+
+    fn get_dead_ends(maze: &Maze) -> Vec<(usize, usize)> {
+        let mut dead_ends: Vec<(usize, usize)> = Vec::new();
+        for row in (1..maze.height - 1).step_by(2) {
+            for col in (1..maze.width - 1).step_by(2) {
+                if Braid::is_dead_end(maze, (col, row)) {
+                    dead_ends.push((col, row));
+                }
+            }
+        }
+        dead_ends
+    }
+
+    pub fn remove_dead_ends(
+        maze: &mut Maze,
+        rng: &mut dyn RngCore,
+        events: &mut dyn MazeEventHandler,
+        dead_end_removal: f64,
+    ) {
+        /*
+        Algorithm:
+
+        1. Collect all dead ends and shuffle them.
+        2. For the given fraction of them: If the cell is still a dead end (removing a wall
+            may also remove a neighboring dead end), remove one of its walls. Prefer walls that
+            lead to another dead end, so that both dead ends are removed at once.
+        */
+        let mut dead_ends = Braid::get_dead_ends(maze);
+        dead_ends.shuffle(rng);
+        let number_of_dead_ends_to_remove =
+            (dead_end_removal.clamp(0.0, 1.0) * dead_ends.len() as f64).round() as usize;
+
+        for &cell in dead_ends.iter().take(number_of_dead_ends_to_remove) {
+            if !Braid::is_dead_end(maze, cell) {
+                continue;
+            }
+            // The walls that can be removed (the border needs to stay).
+            let walls: Vec<((usize, usize), (usize, usize))> = DIRECTIONS
+                .iter()
+                .map(|direction| (direction, direction.apply(cell)))
+                .filter(|&(_, wall)| {
+                    wall.0 > 0
+                        && wall.0 < maze.width - 1
+                        && wall.1 > 0
+                        && wall.1 < maze.height - 1
+                        && maze.is_blocked(wall)
+                })
+                .map(|(direction, wall)| (wall, direction.apply(wall)))
+                .collect();
+            let walls_to_dead_ends: Vec<((usize, usize), (usize, usize))> = walls
+                .iter()
+                .filter(|&&(_, neighbor)| Braid::is_dead_end(maze, neighbor))
+                .cloned()
+                .collect();
+            let Some(&(wall, _)) = walls_to_dead_ends.choose(rng).or_else(|| walls.choose(rng))
+            else {
+                continue;
+            };
+            maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: wall,
+                    highlight: Some(CellColorType::Path),
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: wall,
+                    highlight: None,
+                },
+            );
+        }
+    }
+}
+
+impl MazeGenerator for Braid {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        self.generator.generate(maze, rng, events);
+        Braid::remove_dead_ends(maze, rng, events, self.dead_end_removal);
+    }
+
+    fn initial_state(&self) -> MazeInitialState {
+        self.generator.initial_state()
+    }
+
+    fn to_string(&self) -> String {
+        format!(
+            "{} (braided {}%)",
+            self.generator.to_string(),
+            (self.dead_end_removal * 100.0).round()
+        )
+    }
+}
@@ -0,0 +1,173 @@
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::BTreeMap;
+
+pub struct Eller;
+
+impl Eller {
+    // Returns an iterator over the rows of a maze with the given size. The rows
+    // are created one after another, so only the current row is kept in memory.
+    pub fn rows<R: Rng>(width: usize, height: usize, rng: R) -> EllerRows<R> {
+        if width < 3 || height < 3 || width % 2 != 1 || height % 2 != 1 {
+            panic!("The maze size has to be odd and at least 3x3.");
+        }
+        let number_of_cells = (width - 1) / 2;
+        EllerRows {
+            width,
+            height,
+            rng,
+            sets: (0..number_of_cells).map(Some).collect(),
+            next_set: number_of_cells,
+            row: 0,
+            pending_row: None,
+        }
+    }
+}
+
+impl MazeGenerator for Eller {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Put every cell of the first row into its own set.
+        2. Randomly join adjacent cells of the current row that are not in the same set.
+        3. For each set, randomly create at least one vertical connection to the next row.
+            The connected cells of the next row inherit the set, all others get a new set.
+        4. Repeat steps 2 and 3 for every row until the last row is reached.
+        5. In the last row, join all adjacent cells that are not in the same set.
+        */
+
+        for (row, data_row) in Eller::rows(maze.width, maze.height, rng).enumerate() {
+            maze.data[row] = data_row;
+            for col in 0..maze.width {
+                if maze.is_accessible((col, row)) {
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: (col, row),
+                            highlight: None,
+                        },
+                    );
+                }
+            }
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+        }
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Eller")
+    }
+}
+
+pub struct EllerRows<R: Rng> {
+    width: usize,
+    height: usize,
+    rng: R,
+    // The set of each cell in the current row (None if the cell has no set yet).
+    sets: Vec<Option<usize>>,
+    next_set: usize,
+    // The index of the next row to return.
+    row: usize,
+    // The row below the current row of cells (vertical connections).
+    pending_row: Option<Vec<bool>>,
+}
+
+impl<R: Rng> EllerRows<R> {
+    fn create_cell_row(&mut self) -> (Vec<bool>, Vec<bool>) {
+        let is_last_row = self.row == self.height - 2;
+        let mut cell_row = vec![MAZE_VALUE_BLOCKED; self.width];
+        let mut connection_row = vec![MAZE_VALUE_BLOCKED; self.width];
+
+        // Every cell that is not part of a set gets its own set.
+        for set in self.sets.iter_mut() {
+            if set.is_none() {
+                *set = Some(self.next_set);
+                self.next_set += 1;
+            }
+        }
+        for idx in 0..self.sets.len() {
+            cell_row[2 * idx + 1] = MAZE_VALUE_ACCESSIBLE;
+        }
+
+        // Join adjacent cells. In the last row all cells need to be joined.
+        for idx in 0..self.sets.len() - 1 {
+            if self.sets[idx] != self.sets[idx + 1] && (is_last_row || self.rng.gen_bool(0.5)) {
+                let set_old = self.sets[idx + 1];
+                let set_new = self.sets[idx];
+                self.sets
+                    .iter_mut()
+                    .filter(|set| **set == set_old)
+                    .for_each(|set| *set = set_new);
+                cell_row[2 * idx + 2] = MAZE_VALUE_ACCESSIBLE;
+            }
+        }
+        if is_last_row {
+            return (cell_row, connection_row);
+        }
+
+        // Create at least one vertical connection per set.
+        let mut cells_per_set: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (idx, set) in self.sets.iter().enumerate() {
+            cells_per_set.entry(set.unwrap()).or_default().push(idx);
+        }
+        let mut sets_next_row: Vec<Option<usize>> = vec![None; self.sets.len()];
+        for (set, mut cells) in cells_per_set.into_iter() {
+            cells.shuffle(&mut self.rng);
+            let number_of_connections = self.rng.gen_range(1..=cells.len());
+            for &idx in cells.iter().take(number_of_connections) {
+                connection_row[2 * idx + 1] = MAZE_VALUE_ACCESSIBLE;
+                sets_next_row[idx] = Some(set);
+            }
+        }
+        self.sets = sets_next_row;
+        (cell_row, connection_row)
+    }
+}
+
+impl<R: Rng> Iterator for EllerRows<R> {
+    type Item = Vec<bool>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.height {
+            return None;
+        }
+        let data_row = if self.row == 0 {
+            // The upper border.
+            vec![MAZE_VALUE_BLOCKED; self.width]
+        } else if let Some(pending_row) = self.pending_row.take() {
+            pending_row
+        } else {
+            let (cell_row, connection_row) = self.create_cell_row();
+            self.pending_row = Some(connection_row);
+            cell_row
+        };
+        self.row += 1;
+        Some(data_row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn rows_give_the_generated_maze() {
+        for (width, height) in [(11, 11), (31, 21), (21, 41)] {
+            for seed in 0..3 {
+                let mut maze = Maze::new(width, height, (1, 1));
+                maze.change_size(width, height);
+                maze.generate(&Eller, seed, &mut NullEventHandler);
+
+                let rows: Vec<Vec<bool>> =
+                    Eller::rows(width, height, StdRng::seed_from_u64(seed)).collect();
+                assert_eq!(rows, maze.data, "{}x{} (seed {})", width, height, seed);
+            }
+        }
+    }
+}
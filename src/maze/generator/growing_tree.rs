@@ -0,0 +1,183 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::fmt;
+
+// The probability of choosing the newest cell if the strategy is mixed.
+pub const GROWING_TREE_MIXED_RATIO_DEFAULT: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GrowingTreeStrategy {
+    // Behaves like the recursive backtracking.
+    Newest,
+    Oldest,
+    // Behaves like Prim's algorithm.
+    Random,
+    // Choose the newest cell with the given probability, otherwise a random one.
+    Mixed(f64),
+}
+
+impl fmt::Display for GrowingTreeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Newest => write!(f, "newest"),
+            Self::Oldest => write!(f, "oldest"),
+            Self::Random => write!(f, "random"),
+            Self::Mixed(ratio) => write!(f, "mixed {}%", (ratio * 100.0).round()),
+        }
+    }
+}
+
+pub struct GrowingTree {
+    pub strategy: GrowingTreeStrategy,
+}
+
+impl GrowingTree {
+    fn choose_index(&self, number_of_cells: usize, rng: &mut dyn RngCore) -> usize {
+        match self.strategy {
+            GrowingTreeStrategy::Newest => number_of_cells - 1,
+            GrowingTreeStrategy::Oldest => 0,
+            GrowingTreeStrategy::Random => rng.gen_range(0..number_of_cells),
+            GrowingTreeStrategy::Mixed(ratio) => {
+                if rng.gen_bool(ratio.clamp(0.0, 1.0)) {
+                    number_of_cells - 1
+                } else {
+                    rng.gen_range(0..number_of_cells)
+                }
+            }
+        }
+    }
+}
+
+impl MazeGenerator for GrowingTree {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Choose a random cell, mark it as visited and add it to the list of active cells.
+        2. Choose a cell from the list of active cells (the strategy defines which one).
+        3. If this cell has unvisited neighbors, choose one of them at random, remove the wall
+            between them and add the neighbor to the list of active cells.
+            Otherwise remove the cell from the list of active cells.
+        4. Repeat steps 2 and 3 until the list of active cells is empty.
+        */
+
+        // Choose a random starting cell. The cells are ordered like in the recursive
+        // backtracking, so that with the strategy "newest", the same seed leads to the same maze.
+        let cells: Vec<(usize, usize)> = (1..maze.width - 1)
+            .step_by(2)
+            .flat_map(|col| (1..maze.height - 1).step_by(2).map(move |row| (col, row)))
+            .collect();
+        let start_cell = *cells.choose(rng).unwrap();
+        maze.data[start_cell.1][start_cell.0] = MAZE_VALUE_ACCESSIBLE;
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: start_cell,
+                highlight: Some(CellColorType::InspectedCell),
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+        let mut active_cells: Vec<(usize, usize)> = vec![start_cell];
+
+        while !active_cells.is_empty() {
+            let idx = self.choose_index(active_cells.len(), rng);
+            let current_cell = active_cells[idx];
+            // Determine the possible directions you can choose from.
+            let mut possible_directions: Vec<AbsoluteDirection> = Vec::new();
+            // Look to the left.
+            if current_cell.0 > 1 && maze.is_blocked((current_cell.0 - 2, current_cell.1)) {
+                possible_directions.push(AbsoluteDirection::Left);
+            }
+            // Look to the right.
+            if current_cell.0 < maze.width - 2
+                && maze.is_blocked((current_cell.0 + 2, current_cell.1))
+            {
+                possible_directions.push(AbsoluteDirection::Right);
+            }
+            // Look to the top.
+            if current_cell.1 > 1 && maze.is_blocked((current_cell.0, current_cell.1 - 2)) {
+                possible_directions.push(AbsoluteDirection::Up);
+            }
+            // Look to the bottom.
+            if current_cell.1 < maze.height - 2
+                && maze.is_blocked((current_cell.0, current_cell.1 + 2))
+            {
+                possible_directions.push(AbsoluteDirection::Down);
+            }
+            // If there is no unvisited neighbor, the cell is no longer active.
+            if possible_directions.is_empty() {
+                active_cells.remove(idx);
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: current_cell,
+                        highlight: None,
+                    },
+                );
+                continue;
+            }
+            // Choose a direction to walk to. Therefore we need to take two steps.
+            let direction = possible_directions.choose(rng).unwrap();
+            let wall = direction.apply(current_cell);
+            let next_cell = direction.apply(wall);
+            maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+            maze.data[next_cell.1][next_cell.0] = MAZE_VALUE_ACCESSIBLE;
+            active_cells.push(next_cell);
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: wall,
+                    highlight: None,
+                },
+            );
+            events.handle(
+                maze,
+                MazeEvent::CellCarved {
+                    pos: next_cell,
+                    highlight: Some(CellColorType::InspectedCell),
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+        }
+    }
+
+    fn to_string(&self) -> String {
+        format!("growing tree ({})", self.strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::RecursiveBacktracking;
+
+    #[test]
+    fn newest_strategy_generates_the_recursive_backtracking_maze() {
+        for (width, height) in [(11, 11), (31, 21)] {
+            for seed in 0..5 {
+                let mut maze_growing_tree = Maze::new(width, height, (1, 1));
+                maze_growing_tree.change_size(width, height);
+                let mut maze_backtracking = maze_growing_tree.clone();
+                maze_growing_tree.generate(
+                    &GrowingTree {
+                        strategy: GrowingTreeStrategy::Newest,
+                    },
+                    seed,
+                    &mut NullEventHandler,
+                );
+                maze_backtracking.generate(&RecursiveBacktracking, seed, &mut NullEventHandler);
+                assert_eq!(
+                    maze_growing_tree.data, maze_backtracking.data,
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+    }
+}
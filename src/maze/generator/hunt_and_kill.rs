@@ -0,0 +1,126 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+pub struct HuntAndKill;
+
+impl HuntAndKill {
+    fn get_directions(maze: &Maze, cell: (usize, usize), visited: bool) -> Vec<AbsoluteDirection> {
+        // Returns the directions to the neighboring cells that are (not) visited yet.
+        let mut possible_directions: Vec<AbsoluteDirection> = Vec::new();
+        // Look to the left.
+        if cell.0 > 1 && maze.is_accessible((cell.0 - 2, cell.1)) == visited {
+            possible_directions.push(AbsoluteDirection::Left);
+        }
+        // Look to the right.
+        if cell.0 < maze.width - 2 && maze.is_accessible((cell.0 + 2, cell.1)) == visited {
+            possible_directions.push(AbsoluteDirection::Right);
+        }
+        // Look to the top.
+        if cell.1 > 1 && maze.is_accessible((cell.0, cell.1 - 2)) == visited {
+            possible_directions.push(AbsoluteDirection::Up);
+        }
+        // Look to the bottom.
+        if cell.1 < maze.height - 2 && maze.is_accessible((cell.0, cell.1 + 2)) == visited {
+            possible_directions.push(AbsoluteDirection::Down);
+        }
+        possible_directions
+    }
+
+    fn hunt(maze: &Maze) -> Option<((usize, usize), Vec<AbsoluteDirection>)> {
+        // Scan the maze row by row for an unvisited cell next to a visited one.
+        for row in (1..maze.height - 1).step_by(2) {
+            for col in (1..maze.width - 1).step_by(2) {
+                if maze.is_accessible((col, row)) {
+                    continue;
+                }
+                let possible_directions = HuntAndKill::get_directions(maze, (col, row), true);
+                if !possible_directions.is_empty() {
+                    return Some(((col, row), possible_directions));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl MazeGenerator for HuntAndKill {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Choose a random starting cell.
+        2. Walk: Remove the wall to a random unvisited neighbor and make it the current cell.
+            Repeat until the current cell has no unvisited neighbors.
+        3. Hunt: Scan the maze row by row for the first unvisited cell that is next to a visited
+            cell. Remove the wall between them and make it the current cell.
+        4. Repeat steps 2 and 3 until the hunt does not find any unvisited cell.
+        */
+
+        // Choose a random starting cell.
+        let mut current_cell = (
+            2 * rng.gen_range(0..(maze.width - 1) / 2) + 1,
+            2 * rng.gen_range(0..(maze.height - 1) / 2) + 1,
+        );
+        maze.data[current_cell.1][current_cell.0] = MAZE_VALUE_ACCESSIBLE;
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: current_cell,
+                highlight: None,
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+
+        loop {
+            // Walk.
+            let possible_directions = HuntAndKill::get_directions(maze, current_cell, false);
+            let (direction, cell) = if let Some(direction) = possible_directions.choose(rng) {
+                (*direction, current_cell)
+            } else if let Some((cell, possible_directions)) = HuntAndKill::hunt(maze) {
+                // Hunt. The found cell is connected to an already visited neighbor.
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: cell,
+                        highlight: Some(CellColorType::CurrentCell),
+                    },
+                );
+                events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+                maze.data[cell.1][cell.0] = MAZE_VALUE_ACCESSIBLE;
+                (*possible_directions.choose(rng).unwrap(), cell)
+            } else {
+                break;
+            };
+            // Remove the wall. Therefore we need to take two steps.
+            let wall = direction.apply(cell);
+            let next_cell = direction.apply(wall);
+            maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+            maze.data[next_cell.1][next_cell.0] = MAZE_VALUE_ACCESSIBLE;
+            for pos in [cell, wall, next_cell] {
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos,
+                        highlight: None,
+                    },
+                );
+            }
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+            // After the hunt, we continue walking from the found cell.
+            current_cell = if cell == current_cell {
+                next_cell
+            } else {
+                cell
+            };
+        }
+    }
+
+    fn to_string(&self) -> String {
+        String::from("hunt and kill")
+    }
+}
@@ -1,18 +1,15 @@
-use crate::maze::animation::delay;
 use crate::maze::animation::*;
-use crate::maze::draw::{
-    draw_character, get_unique_colors, highlight_cells_by_rgb_color, SYMBOL_MAZE_FIELD_ACCESSIBLE,
-};
+use crate::maze::draw::get_unique_colors;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
 use crate::maze::maze::*;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::io::Write;
+use rand::RngCore;
 
 pub struct Kruskal;
 
 impl MazeGenerator for Kruskal {
-    fn generate(&self, maze: &mut Maze, screen: &mut dyn Write, animate: bool) {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
         /*
         Algorithm:
 
@@ -37,14 +34,19 @@ impl MazeGenerator for Kruskal {
             for col in (1..maze.width - 1).step_by(2) {
                 forest.push(vec![(row, col)]);
                 maze.data[row][col] = MAZE_VALUE_ACCESSIBLE;
-                if animate {
-                    draw_character(screen, maze, (col, row), SYMBOL_MAZE_FIELD_ACCESSIBLE, None);
-                    delay(Delay::Short);
-                }
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: (col, row),
+                        highlight: None,
+                    },
+                );
+                events.handle(maze, MazeEvent::Pause(Delay::Short));
             }
         }
 
         // Now we have all the trees visualized. Give them all a (almost) unique color.
+        let animate = events.animate();
         let mut colors = if animate {
             get_unique_colors(forest.len())
         } else {
@@ -52,10 +54,16 @@ impl MazeGenerator for Kruskal {
         };
         if animate {
             for (idx, tree) in forest.iter().enumerate() {
-                highlight_cells_by_rgb_color(screen, maze, tree.clone(), colors[idx]);
+                events.handle(
+                    maze,
+                    MazeEvent::CellsColored {
+                        positions: to_positions(tree),
+                        color: colors[idx],
+                    },
+                );
             }
             for _ in 0..30 {
-                delay(GENERATION_DELAY);
+                events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
             }
         }
 
@@ -73,7 +81,7 @@ impl MazeGenerator for Kruskal {
             }
         }
         // Shuffle them.
-        edges.shuffle(&mut thread_rng());
+        edges.shuffle(rng);
 
         while forest.len() > 1 {
             let (ce_row, ce_col) = edges[0];
@@ -146,15 +154,19 @@ impl MazeGenerator for Kruskal {
                     colors.push(colors[tree2]);
                     colors.remove(tree2);
                     colors.remove(tree1);
-                    highlight_cells_by_rgb_color(screen, maze, new_tree, colors[colors.len() - 1]);
-                    delay(GENERATION_DELAY);
+                    events.handle(
+                        maze,
+                        MazeEvent::CellsColored {
+                            positions: to_positions(&new_tree),
+                            color: colors[colors.len() - 1],
+                        },
+                    );
+                    events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
                 }
             }
         }
-        if animate {
-            for _ in 0..30 {
-                delay(GENERATION_DELAY);
-            }
+        for _ in 0..30 {
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
         }
     }
 
@@ -162,3 +174,8 @@ impl MazeGenerator for Kruskal {
         String::from("Kruskal")
     }
 }
+
+// The trees store (row, col), the events expect (x, y).
+fn to_positions(tree: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    tree.iter().map(|&(row, col)| (col, row)).collect()
+}
@@ -1,44 +1,247 @@
 use crate::maze::animation::Delay;
-use crate::maze::maze::Maze;
-use std::io::Write;
+use crate::maze::event::MazeEventHandler;
+use crate::maze::maze::{Maze, MazeInitialState};
+use rand::RngCore;
+use std::fmt;
 
 pub const GENERATION_DELAY: Delay = Delay::Long;
 
+pub mod aldous_broder;
+pub mod binary_tree;
+pub mod braid;
+pub mod eller;
+pub mod growing_tree;
+pub mod hunt_and_kill;
 pub mod kruskal;
+pub mod prim;
 pub mod recursive_backtracking;
+pub mod recursive_division;
+pub mod sidewinder;
+pub mod terrain;
 pub mod wilson;
 
+pub use aldous_broder::AldousBroder;
+pub use binary_tree::{BinaryTree, DiagonalBias};
+pub use braid::Braid;
+pub use eller::Eller;
+pub use growing_tree::{GrowingTree, GrowingTreeStrategy, GROWING_TREE_MIXED_RATIO_DEFAULT};
+pub use hunt_and_kill::HuntAndKill;
 pub use kruskal::Kruskal;
+pub use prim::Prim;
 pub use recursive_backtracking::RecursiveBacktracking;
+pub use recursive_division::RecursiveDivision;
+pub use sidewinder::Sidewinder;
+pub use terrain::{Terrain, TerrainType};
 pub use wilson::Wilson;
 
 pub trait MazeGenerator: Send + Sync {
-    fn generate(&self, maze: &mut Maze, screen: &mut dyn Write, animate: bool);
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler);
+
+    fn initial_state(&self) -> MazeInitialState {
+        MazeInitialState::Blocked
+    }
 
     fn to_string(&self) -> String;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MazeGenerationAlgorithms {
+    AldousBroder,
+    BinaryTree(DiagonalBias),
+    Eller,
+    GrowingTree(GrowingTreeStrategy),
+    HuntAndKill,
     Kruskal,
+    Prim,
     RecursiveBacktracking,
+    RecursiveDivision,
+    Sidewinder(DiagonalBias),
     Wilson,
 }
 
 impl MazeGenerationAlgorithms {
     pub fn next(&self) -> Self {
         match self {
-            Self::Kruskal => Self::RecursiveBacktracking,
-            Self::RecursiveBacktracking => Self::Wilson,
-            Self::Wilson => Self::Kruskal,
+            Self::AldousBroder => Self::BinaryTree(DiagonalBias::NorthEast),
+            Self::BinaryTree(bias) => match bias.next() {
+                Some(bias) => Self::BinaryTree(bias),
+                None => Self::Eller,
+            },
+            Self::Eller => Self::GrowingTree(GrowingTreeStrategy::Newest),
+            Self::GrowingTree(strategy) => match strategy {
+                GrowingTreeStrategy::Newest => Self::GrowingTree(GrowingTreeStrategy::Oldest),
+                GrowingTreeStrategy::Oldest => Self::GrowingTree(GrowingTreeStrategy::Random),
+                GrowingTreeStrategy::Random => {
+                    Self::GrowingTree(GrowingTreeStrategy::Mixed(GROWING_TREE_MIXED_RATIO_DEFAULT))
+                }
+                GrowingTreeStrategy::Mixed(_) => Self::HuntAndKill,
+            },
+            Self::HuntAndKill => Self::Kruskal,
+            Self::Kruskal => Self::Prim,
+            Self::Prim => Self::RecursiveBacktracking,
+            Self::RecursiveBacktracking => Self::RecursiveDivision,
+            Self::RecursiveDivision => Self::Sidewinder(DiagonalBias::NorthEast),
+            Self::Sidewinder(bias) => match bias.next() {
+                Some(bias) => Self::Sidewinder(bias),
+                None => Self::Wilson,
+            },
+            Self::Wilson => Self::AldousBroder,
+        }
+    }
+
+    // All algorithms in the order they are cycled through.
+    pub fn all() -> Vec<Self> {
+        let mut algorithms = vec![Self::AldousBroder];
+        loop {
+            let algorithm = algorithms.last().unwrap().next();
+            if let Self::AldousBroder = algorithm {
+                return algorithms;
+            }
+            algorithms.push(algorithm);
+        }
+    }
+
+    // Uses the given ratio if the algorithm is the mixed growing tree.
+    pub fn with_mixed_ratio(self, ratio: f64) -> Self {
+        match self {
+            Self::GrowingTree(GrowingTreeStrategy::Mixed(_)) => {
+                Self::GrowingTree(GrowingTreeStrategy::Mixed(ratio))
+            }
+            _ => self,
+        }
+    }
+
+    // The name used on the command line, e.g. "binary-tree-ne". The ratio of the mixed growing
+    // tree is not part of it (see with_mixed_ratio).
+    pub fn to_command_line_name(&self) -> String {
+        if let Self::GrowingTree(GrowingTreeStrategy::Mixed(_)) = self {
+            return String::from("growing-tree-mixed");
+        }
+        self.to_string()
+            .to_lowercase()
+            .replace(['(', ')'], "")
+            .replace(' ', "-")
+    }
+
+    pub fn from_command_line_name(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|algorithm| algorithm.to_command_line_name() == name)
+    }
+
+    pub fn to_generator(&self) -> Box<dyn MazeGenerator> {
+        match self {
+            Self::AldousBroder => Box::new(AldousBroder),
+            Self::BinaryTree(bias) => Box::new(BinaryTree { bias: *bias }),
+            Self::Eller => Box::new(Eller),
+            Self::GrowingTree(strategy) => Box::new(GrowingTree {
+                strategy: *strategy,
+            }),
+            Self::HuntAndKill => Box::new(HuntAndKill),
+            Self::Kruskal => Box::new(Kruskal),
+            Self::Prim => Box::new(Prim),
+            Self::RecursiveBacktracking => Box::new(RecursiveBacktracking),
+            Self::RecursiveDivision => Box::new(RecursiveDivision),
+            Self::Sidewinder(bias) => Box::new(Sidewinder { bias: *bias }),
+            Self::Wilson => Box::new(Wilson),
         }
     }
+}
 
-    pub fn to_string(&self) -> &str {
+impl fmt::Display for MazeGenerationAlgorithms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Kruskal => "kruskal",
-            Self::RecursiveBacktracking => "recursive backtracking",
-            Self::Wilson => "wilson",
+            Self::AldousBroder => write!(f, "aldous-broder"),
+            Self::BinaryTree(bias) => match bias {
+                DiagonalBias::NorthEast => write!(f, "binary tree (NE)"),
+                DiagonalBias::NorthWest => write!(f, "binary tree (NW)"),
+                DiagonalBias::SouthEast => write!(f, "binary tree (SE)"),
+                DiagonalBias::SouthWest => write!(f, "binary tree (SW)"),
+            },
+            Self::Eller => write!(f, "eller"),
+            Self::GrowingTree(strategy) => write!(f, "growing tree ({})", strategy),
+            Self::HuntAndKill => write!(f, "hunt and kill"),
+            Self::Kruskal => write!(f, "kruskal"),
+            Self::Prim => write!(f, "prim"),
+            Self::RecursiveBacktracking => write!(f, "recursive backtracking"),
+            Self::RecursiveDivision => write!(f, "recursive division"),
+            Self::Sidewinder(bias) => match bias {
+                DiagonalBias::NorthEast => write!(f, "sidewinder (NE)"),
+                DiagonalBias::NorthWest => write!(f, "sidewinder (NW)"),
+                DiagonalBias::SouthEast => write!(f, "sidewinder (SE)"),
+                DiagonalBias::SouthWest => write!(f, "sidewinder (SW)"),
+            },
+            Self::Wilson => write!(f, "wilson"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::direction::AbsoluteDirection;
+    use crate::maze::event::NullEventHandler;
+
+    // Checks that the maze is perfect: all accessible fields are connected and there are no
+    // loops (a tree with n fields has exactly n - 1 passages between them).
+    fn assert_perfect(maze: &Maze, name: &str) {
+        let fields: Vec<(usize, usize)> = (0..maze.height)
+            .flat_map(|y| (0..maze.width).map(move |x| (x, y)))
+            .filter(|&pos| maze.is_accessible(pos))
+            .collect();
+        let passages = fields
+            .iter()
+            .flat_map(|&pos| {
+                [AbsoluteDirection::Right, AbsoluteDirection::Down]
+                    .map(|direction| direction.apply(pos))
+            })
+            .filter(|&pos| maze.is_accessible(pos))
+            .count();
+        assert_eq!(passages + 1, fields.len(), "{}", name);
+
+        let mut visited = vec![vec![false; maze.width]; maze.height];
+        visited[fields[0].1][fields[0].0] = true;
+        let mut stack = vec![fields[0]];
+        let mut number_of_visited = 0;
+        while let Some(pos) = stack.pop() {
+            number_of_visited += 1;
+            for direction in [
+                AbsoluteDirection::Right,
+                AbsoluteDirection::Down,
+                AbsoluteDirection::Left,
+                AbsoluteDirection::Up,
+            ] {
+                let pos_next = direction.apply(pos);
+                if maze.is_accessible(pos_next) && !visited[pos_next.1][pos_next.0] {
+                    visited[pos_next.1][pos_next.0] = true;
+                    stack.push(pos_next);
+                }
+            }
+        }
+        assert_eq!(number_of_visited, fields.len(), "{}", name);
+    }
+
+    #[test]
+    fn every_generator_produces_a_perfect_maze() {
+        for algorithm in MazeGenerationAlgorithms::all() {
+            for (width, height) in [(11, 11), (31, 21), (21, 41)] {
+                for seed in 0..3 {
+                    let mut maze = Maze::new(width, height, (1, 1));
+                    maze.change_size(width, height);
+                    maze.generate(&*algorithm.to_generator(), seed, &mut NullEventHandler);
+                    assert_eq!((maze.width, maze.height), (width, height));
+                    // Every cell (odd row and column) is part of the maze.
+                    for y in (1..height).step_by(2) {
+                        for x in (1..width).step_by(2) {
+                            assert!(maze.is_accessible((x, y)), "{}", algorithm);
+                        }
+                    }
+                    assert_perfect(
+                        &maze,
+                        &format!("{} {}x{} (seed {})", algorithm, width, height, seed),
+                    );
+                }
+            }
         }
     }
 }
@@ -0,0 +1,121 @@
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+pub struct Prim;
+
+impl Prim {
+    fn get_neighbors(maze: &Maze, cell: (usize, usize)) -> Vec<(usize, usize)> {
+        // Returns the cells (not the walls) around the given cell.
+        let mut neighbors: Vec<(usize, usize)> = Vec::new();
+        if cell.0 > 1 {
+            neighbors.push((cell.0 - 2, cell.1));
+        }
+        if cell.0 < maze.width - 2 {
+            neighbors.push((cell.0 + 2, cell.1));
+        }
+        if cell.1 > 1 {
+            neighbors.push((cell.0, cell.1 - 2));
+        }
+        if cell.1 < maze.height - 2 {
+            neighbors.push((cell.0, cell.1 + 2));
+        }
+        neighbors
+    }
+}
+
+impl MazeGenerator for Prim {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Choose a random cell and add it to the maze. Add its neighboring cells to the frontier.
+        2. Choose a random cell from the frontier and connect it to a random neighboring cell
+            that is already part of the maze.
+        3. Add the neighbors of the chosen cell, that are neither part of the maze nor of the
+            frontier, to the frontier.
+        4. Repeat steps 2 and 3 until the frontier is empty.
+        */
+
+        // Get all cells.
+        let mut cells: Vec<(usize, usize)> = Vec::new();
+        for row in (1..maze.height - 1).step_by(2) {
+            for col in (1..maze.width - 1).step_by(2) {
+                cells.push((col, row));
+            }
+        }
+
+        // Choose a random starting cell.
+        let start_cell = *cells.choose(rng).unwrap();
+        maze.data[start_cell.1][start_cell.0] = MAZE_VALUE_ACCESSIBLE;
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: start_cell,
+                highlight: None,
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+
+        // The frontier is a list (to be able to pick a random element) and a set
+        // (to be able to check fast if a cell is already part of it).
+        let mut frontier: Vec<(usize, usize)> = Vec::new();
+        let mut frontier_set: HashSet<(usize, usize)> = HashSet::new();
+        let mut current_cell = start_cell;
+
+        loop {
+            // Add the unvisited neighbors of the current cell to the frontier.
+            for neighbor in Prim::get_neighbors(maze, current_cell) {
+                if maze.is_blocked(neighbor) && !frontier_set.contains(&neighbor) {
+                    frontier.push(neighbor);
+                    frontier_set.insert(neighbor);
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: neighbor,
+                            highlight: Some(CellColorType::InspectedCell),
+                        },
+                    );
+                }
+            }
+            if frontier.is_empty() {
+                break;
+            }
+
+            // Choose a random cell from the frontier.
+            current_cell = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            frontier_set.remove(&current_cell);
+
+            // Connect it to a random neighbor that is already part of the maze.
+            let neighbor = *Prim::get_neighbors(maze, current_cell)
+                .into_iter()
+                .filter(|&neighbor| maze.is_accessible(neighbor))
+                .collect::<Vec<(usize, usize)>>()
+                .choose(rng)
+                .unwrap();
+            let wall = (
+                (current_cell.0 + neighbor.0) / 2,
+                (current_cell.1 + neighbor.1) / 2,
+            );
+            for pos in [wall, current_cell] {
+                maze.data[pos.1][pos.0] = MAZE_VALUE_ACCESSIBLE;
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos,
+                        highlight: None,
+                    },
+                );
+            }
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+        }
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Prim")
+    }
+}
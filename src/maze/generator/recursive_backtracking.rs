@@ -1,16 +1,15 @@
-use crate::maze::animation::delay;
 use crate::maze::direction::AbsoluteDirection;
-use crate::maze::draw::{draw_character, SYMBOL_MAZE_FIELD_ACCESSIBLE};
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
 use crate::maze::maze::*;
 use rand::seq::SliceRandom;
-use std::collections::HashSet;
-use std::io::Write;
+use rand::RngCore;
+use std::collections::BTreeSet;
 
 pub struct RecursiveBacktracking;
 
 impl MazeGenerator for RecursiveBacktracking {
-    fn generate(&self, maze: &mut Maze, screen: &mut dyn Write, animate: bool) {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
         /*
         Algorithm:
 
@@ -21,11 +20,11 @@ impl MazeGenerator for RecursiveBacktracking {
         4. Stop when the algorithm has backed all the way up to the starting cell.
         */
 
-        // Get all unvisited cells.
-        let mut unvisited_cells: HashSet<(usize, usize)> = HashSet::new();
+        // Get all unvisited cells (ordered, so that the seed alone determines the maze).
+        let mut unvisited_cells: BTreeSet<(usize, usize)> = BTreeSet::new();
         for row in (1..maze.height - 1).step_by(2) {
             for col in (1..maze.width - 1).step_by(2) {
-                unvisited_cells.insert((row, col));
+                unvisited_cells.insert((col, row));
             }
         }
 
@@ -34,20 +33,18 @@ impl MazeGenerator for RecursiveBacktracking {
             .clone()
             .into_iter()
             .collect::<Vec<(usize, usize)>>()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .unwrap()
             .clone();
         maze.data[current_cell.1][current_cell.0] = MAZE_VALUE_ACCESSIBLE;
-        if animate {
-            draw_character(
-                screen,
-                maze,
-                current_cell,
-                SYMBOL_MAZE_FIELD_ACCESSIBLE,
-                None,
-            );
-            delay(GENERATION_DELAY);
-        }
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: current_cell,
+                highlight: None,
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
 
         // Remove the starting cell from the unvisited cells.
         unvisited_cells.remove(&current_cell);
@@ -84,23 +81,21 @@ impl MazeGenerator for RecursiveBacktracking {
                 continue;
             }
             // Choose a direction to walk to.
-            let direction = possible_directions.choose(&mut rand::thread_rng()).unwrap();
+            let direction = possible_directions.choose(rng).unwrap();
             // Remember the current position, add it to the path.
             path.push(current_cell);
             // Go to the next cell. Therefore we need to take two steps.
             for _ in 0..2 {
                 current_cell = direction.apply(current_cell);
                 maze.data[current_cell.1][current_cell.0] = MAZE_VALUE_ACCESSIBLE;
-                if animate {
-                    draw_character(
-                        screen,
-                        maze,
-                        current_cell,
-                        SYMBOL_MAZE_FIELD_ACCESSIBLE,
-                        None,
-                    );
-                    delay(GENERATION_DELAY);
-                }
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: current_cell,
+                        highlight: None,
+                    },
+                );
+                events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
             }
             unvisited_cells.remove(&current_cell);
         }
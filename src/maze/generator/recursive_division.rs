@@ -0,0 +1,85 @@
+use crate::maze::animation::Delay;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::{Rng, RngCore};
+
+pub struct RecursiveDivision;
+
+impl RecursiveDivision {
+    fn divide(
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+        rng: &mut dyn RngCore,
+        pos_from: (usize, usize),
+        pos_to: (usize, usize),
+    ) {
+        // The chamber goes from pos_from to pos_to (both are cells and inclusive).
+        let width = pos_to.0 - pos_from.0;
+        let height = pos_to.1 - pos_from.1;
+        if width < 2 || height < 2 {
+            // The chamber is only one cell wide or high, there is nothing to divide.
+            return;
+        }
+        // Divide the chamber along its longer side.
+        let divide_horizontally = match width.cmp(&height) {
+            std::cmp::Ordering::Less => true,
+            std::cmp::Ordering::Greater => false,
+            std::cmp::Ordering::Equal => rng.gen_bool(0.5),
+        };
+        if divide_horizontally {
+            // The wall is on an even row, the gap on an odd column.
+            let row = pos_from.1 + 2 * rng.gen_range(0..height / 2) + 1;
+            let col_gap = pos_from.0 + 2 * rng.gen_range(0..=width / 2);
+            let wall: Vec<(usize, usize)> = (pos_from.0..=pos_to.0)
+                .filter(|&col| col != col_gap)
+                .map(|col| (col, row))
+                .collect();
+            RecursiveDivision::add_wall(maze, events, wall);
+            RecursiveDivision::divide(maze, events, rng, pos_from, (pos_to.0, row - 1));
+            RecursiveDivision::divide(maze, events, rng, (pos_from.0, row + 1), pos_to);
+        } else {
+            // The wall is on an even column, the gap on an odd row.
+            let col = pos_from.0 + 2 * rng.gen_range(0..width / 2) + 1;
+            let row_gap = pos_from.1 + 2 * rng.gen_range(0..=height / 2);
+            let wall: Vec<(usize, usize)> = (pos_from.1..=pos_to.1)
+                .filter(|&row| row != row_gap)
+                .map(|row| (col, row))
+                .collect();
+            RecursiveDivision::add_wall(maze, events, wall);
+            RecursiveDivision::divide(maze, events, rng, pos_from, (col - 1, pos_to.1));
+            RecursiveDivision::divide(maze, events, rng, (col + 1, pos_from.1), pos_to);
+        }
+    }
+
+    fn add_wall(maze: &mut Maze, events: &mut dyn MazeEventHandler, wall: Vec<(usize, usize)>) {
+        for pos in wall {
+            maze.data[pos.1][pos.0] = MAZE_VALUE_BLOCKED;
+            events.handle(maze, MazeEvent::WallAdded { pos });
+            events.handle(maze, MazeEvent::Pause(Delay::Short));
+        }
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+    }
+}
+
+impl MazeGenerator for RecursiveDivision {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Start with an open field that is only surrounded by the border.
+        2. Divide the field with a horizontal or vertical wall and leave a single gap in the wall.
+        3. Repeat step 2 for both resulting chambers until the chambers are only
+            one cell wide or high.
+        */
+        RecursiveDivision::divide(maze, events, rng, (1, 1), (maze.width - 2, maze.height - 2));
+    }
+
+    fn initial_state(&self) -> MazeInitialState {
+        MazeInitialState::OpenWithBorder
+    }
+
+    fn to_string(&self) -> String {
+        String::from("recursive division")
+    }
+}
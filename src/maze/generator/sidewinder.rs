@@ -0,0 +1,113 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::binary_tree::has_neighbor;
+use crate::maze::generator::{DiagonalBias, MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+pub struct Sidewinder {
+    pub bias: DiagonalBias,
+}
+
+impl MazeGenerator for Sidewinder {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. Walk through every row in the horizontal direction of the bias and add the
+            cells to the current run.
+        2. Randomly decide to either remove the wall in the horizontal direction (continue
+            the run) or to close the run. Closing the run means choosing a random cell of the run
+            and removing the wall in the vertical direction of the bias.
+        3. The run is always closed at the end of the row.
+        4. The row at the border in the vertical direction of the bias has no walls
+            in the horizontal direction.
+        */
+        let vertical_direction = self.bias.vertical_direction();
+        let horizontal_direction = self.bias.horizontal_direction();
+
+        let mut cols: Vec<usize> = (1..maze.width - 1).step_by(2).collect();
+        if horizontal_direction == AbsoluteDirection::Left {
+            cols.reverse();
+        }
+        for row in (1..maze.height - 1).step_by(2) {
+            let mut run: Vec<(usize, usize)> = Vec::new();
+            for &col in cols.iter() {
+                let cell = (col, row);
+                maze.data[cell.1][cell.0] = MAZE_VALUE_ACCESSIBLE;
+                run.push(cell);
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos: cell,
+                        highlight: Some(CellColorType::InspectedCell),
+                    },
+                );
+                events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+                let is_end_of_row = !has_neighbor(maze, cell, horizontal_direction);
+                let is_border_row = !has_neighbor(maze, cell, vertical_direction);
+                // The row at the border is one long run, that cannot be closed.
+                let continue_run = !is_end_of_row && (is_border_row || rng.gen_bool(0.5));
+                if continue_run {
+                    let wall = horizontal_direction.apply(cell);
+                    maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: wall,
+                            highlight: Some(CellColorType::InspectedCell),
+                        },
+                    );
+                } else if !is_border_row {
+                    // Close the run.
+                    let cell_run = *run.choose(rng).unwrap();
+                    let wall = vertical_direction.apply(cell_run);
+                    maze.data[wall.1][wall.0] = MAZE_VALUE_ACCESSIBLE;
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: wall,
+                            highlight: None,
+                        },
+                    );
+                    for pos in complete_run(&run) {
+                        events.handle(
+                            maze,
+                            MazeEvent::CellCarved {
+                                pos,
+                                highlight: None,
+                            },
+                        );
+                    }
+                    events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+                    run.clear();
+                }
+            }
+            for pos in complete_run(&run) {
+                events.handle(
+                    maze,
+                    MazeEvent::CellCarved {
+                        pos,
+                        highlight: None,
+                    },
+                );
+            }
+        }
+    }
+
+    fn to_string(&self) -> String {
+        format!("sidewinder ({})", self.bias)
+    }
+}
+
+// Returns the cells of the run including the walls between them.
+fn complete_run(run: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let (Some(first), Some(last)) = (run.first(), run.last()) else {
+        return Vec::new();
+    };
+    (first.0.min(last.0)..=first.0.max(last.0))
+        .map(|col| (col, first.1))
+        .collect()
+}
@@ -0,0 +1,125 @@
+use crate::maze::draw::cost_to_rgb;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
+use crate::maze::maze::*;
+use rand::{Rng, RngCore};
+
+// On average, there is one patch of terrain per this many fields of the maze.
+const TERRAIN_FIELDS_PER_PATCH: usize = 60;
+const TERRAIN_PATCH_RADIUS_MAX: usize = 3;
+
+// The kinds of terrain that slow down walking through a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerrainType {
+    Grass,
+    Mud,
+    Water,
+}
+
+impl TerrainType {
+    pub fn all() -> [Self; 3] {
+        [Self::Grass, Self::Mud, Self::Water]
+    }
+
+    pub fn cost(&self) -> usize {
+        match self {
+            Self::Grass => 2,
+            Self::Mud => 4,
+            Self::Water => 8,
+        }
+    }
+
+    pub fn from_cost(cost: usize) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|terrain_type| terrain_type.cost() == cost)
+    }
+
+    // The character of the terrain in the text format.
+    pub fn to_symbol(self) -> char {
+        match self {
+            Self::Grass => '░',
+            Self::Mud => '▒',
+            Self::Water => '▓',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|terrain_type| terrain_type.to_symbol() == symbol)
+    }
+}
+
+// Wraps another generator and covers the generated maze with random patches of terrain
+// (grass, mud and water), so walking through them costs more than walking on the ground.
+pub struct Terrain {
+    pub generator: Box<dyn MazeGenerator>,
+}
+
+impl Terrain {
+    // Only wraps the generator if the terrain is wanted.
+    pub fn wrap(generator: Box<dyn MazeGenerator>, terrain: bool) -> Box<dyn MazeGenerator> {
+        if terrain {
+            Box::new(Terrain { generator })
+        } else {
+            generator
+        }
+    }
+
+    pub fn add_terrain(maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        /*
+        Algorithm:
+
+        1. The number of patches depends on the size of the maze.
+        2. For every patch, choose a random center, a random radius and a random type of terrain.
+        3. All accessible fields within the radius get the cost of the terrain. Later patches
+            overwrite earlier ones.
+        */
+        let number_of_patches = (maze.width * maze.height / TERRAIN_FIELDS_PER_PATCH).max(1);
+        for _ in 0..number_of_patches {
+            let center = (
+                rng.gen_range(1..maze.width - 1),
+                rng.gen_range(1..maze.height - 1),
+            );
+            let radius = rng.gen_range(1..=TERRAIN_PATCH_RADIUS_MAX);
+            let terrain_type = TerrainType::all()[rng.gen_range(0..TerrainType::all().len())];
+            let mut positions: Vec<(usize, usize)> = Vec::new();
+            for row in center.1.saturating_sub(radius)..=(center.1 + radius).min(maze.height - 1) {
+                for col in center.0.saturating_sub(radius)..=(center.0 + radius).min(maze.width - 1)
+                {
+                    if col.abs_diff(center.0).pow(2) + row.abs_diff(center.1).pow(2)
+                        <= radius.pow(2)
+                        && maze.is_accessible((col, row))
+                    {
+                        maze.costs[row][col] = terrain_type.cost();
+                        positions.push((col, row));
+                    }
+                }
+            }
+            events.handle(
+                maze,
+                MazeEvent::CellsColored {
+                    positions,
+                    color: cost_to_rgb(terrain_type.cost()),
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
+        }
+    }
+}
+
+impl MazeGenerator for Terrain {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
+        self.generator.generate(maze, rng, events);
+        Terrain::add_terrain(maze, rng, events);
+    }
+
+    fn initial_state(&self) -> MazeInitialState {
+        self.generator.initial_state()
+    }
+
+    fn to_string(&self) -> String {
+        format!("{} (with terrain)", self.generator.to_string())
+    }
+}
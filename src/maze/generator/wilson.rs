@@ -1,17 +1,16 @@
-use crate::maze::animation::delay;
 use crate::maze::direction::AbsoluteDirection;
-use crate::maze::draw::{draw_character, SYMBOL_MAZE_FIELD_ACCESSIBLE};
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::generator::{MazeGenerator, GENERATION_DELAY};
 use crate::maze::maze::*;
 use crate::maze::path::complete_path;
 use rand::seq::SliceRandom;
-use std::collections::HashSet;
-use std::io::Write;
+use rand::RngCore;
+use std::collections::BTreeSet;
 
 pub struct Wilson;
 
 impl MazeGenerator for Wilson {
-    fn generate(&self, maze: &mut Maze, screen: &mut dyn Write, animate: bool) {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, events: &mut dyn MazeEventHandler) {
         /*
         Algorithm:
         1. Choose a random cell and add it to the Uniform Spanning Tree (UST).
@@ -20,11 +19,11 @@ impl MazeGenerator for Wilson {
         4. Repeat steps 2 and 3 until all cells have been added to the UST.
         */
 
-        // Get all unvisited cells.
-        let mut unvisited_cells: HashSet<(usize, usize)> = HashSet::new();
+        // Get all unvisited cells (ordered, so that the seed alone determines the maze).
+        let mut unvisited_cells: BTreeSet<(usize, usize)> = BTreeSet::new();
         for row in (1..maze.height - 1).step_by(2) {
             for col in (1..maze.width - 1).step_by(2) {
-                unvisited_cells.insert((row, col));
+                unvisited_cells.insert((col, row));
             }
         }
 
@@ -33,23 +32,27 @@ impl MazeGenerator for Wilson {
             .clone()
             .into_iter()
             .collect::<Vec<(usize, usize)>>()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .unwrap()
             .clone();
         maze.data[start_cell.1][start_cell.0] = MAZE_VALUE_ACCESSIBLE;
         // Remove the starting cell from the unvisited cells.
         unvisited_cells.remove(&start_cell);
-        if animate {
-            draw_character(screen, maze, start_cell, SYMBOL_MAZE_FIELD_ACCESSIBLE, None);
-            delay(GENERATION_DELAY);
-        }
+        events.handle(
+            maze,
+            MazeEvent::CellCarved {
+                pos: start_cell,
+                highlight: None,
+            },
+        );
+        events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
 
         // Choose a random cell to start the random walk from.
         let mut current_cell = unvisited_cells
             .clone()
             .into_iter()
             .collect::<Vec<(usize, usize)>>()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .unwrap()
             .clone();
 
@@ -82,7 +85,7 @@ impl MazeGenerator for Wilson {
                     .clone()
                     .into_iter()
                     .collect::<Vec<(usize, usize)>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(rng)
                     .unwrap()
                     .clone();
                 path.clear();
@@ -90,7 +93,7 @@ impl MazeGenerator for Wilson {
                 continue;
             }
             // Choose a direction to walk to.
-            let direction = possible_directions.choose(&mut rand::thread_rng()).unwrap();
+            let direction = possible_directions.choose(rng).unwrap();
             // Go to the next cell. Therefore we need to take two steps.
             for _ in 0..2 {
                 current_cell = direction.apply(current_cell);
@@ -107,10 +110,14 @@ impl MazeGenerator for Wilson {
                 path = complete_path(path);
                 for pos in path.iter() {
                     maze.data[pos.1][pos.0] = MAZE_VALUE_ACCESSIBLE;
-                    if animate {
-                        draw_character(screen, maze, *pos, SYMBOL_MAZE_FIELD_ACCESSIBLE, None);
-                        delay(GENERATION_DELAY);
-                    }
+                    events.handle(
+                        maze,
+                        MazeEvent::CellCarved {
+                            pos: *pos,
+                            highlight: None,
+                        },
+                    );
+                    events.handle(maze, MazeEvent::Pause(GENERATION_DELAY));
                 }
                 unvisited_cells.retain(|&x| !path.contains(&x));
                 if unvisited_cells.is_empty() {
@@ -120,7 +127,7 @@ impl MazeGenerator for Wilson {
                     .clone()
                     .into_iter()
                     .collect::<Vec<(usize, usize)>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(rng)
                     .unwrap()
                     .clone();
                 path.clear();
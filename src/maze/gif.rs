@@ -0,0 +1,260 @@
+use crate::maze::animation::virtual_time;
+use crate::maze::virtual_terminal::{TerminalCell, VirtualTerminal};
+use std::collections::HashMap;
+use std::io::{Result, Write};
+use std::time::Duration;
+
+// Most viewers do not show frames shorter than this, so faster changes are merged.
+const GIF_FRAME_DURATION_MIN: Duration = Duration::from_millis(20);
+pub const GIF_CELL_SIZE_DEFAULT: usize = 8;
+const GIF_PALETTE_SIZE_MAX: usize = 256;
+
+// The part of the terminal that changed since the previous frame.
+struct RecordedFrame {
+    time: Duration,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    cells: Vec<TerminalCell>,
+}
+
+// Records everything written to the screen into an animated GIF. The frames are timed by the
+// virtual clock, so the animations need to run with the virtual clock started.
+pub struct GifRecorder {
+    terminal: VirtualTerminal,
+    // The content of the terminal in the last recorded frame.
+    recorded_cells: Vec<Vec<TerminalCell>>,
+    frames: Vec<RecordedFrame>,
+    time_last_write: Duration,
+}
+
+impl GifRecorder {
+    pub fn new(width: usize, height: usize) -> Self {
+        GifRecorder {
+            terminal: VirtualTerminal::new(width, height),
+            recorded_cells: vec![vec![TerminalCell::default(); width]; height],
+            frames: Vec::new(),
+            time_last_write: Duration::ZERO,
+        }
+    }
+
+    fn record_frame(&mut self, time: Duration, force: bool) {
+        let mut time = time;
+        if let Some(frame_prev) = self.frames.last() {
+            if time < frame_prev.time + GIF_FRAME_DURATION_MIN {
+                if !force {
+                    return;
+                }
+                time = frame_prev.time + GIF_FRAME_DURATION_MIN;
+            }
+        }
+        // Find the rectangle that contains all changes.
+        let changed_positions: Vec<(usize, usize)> = (0..self.terminal.height)
+            .flat_map(|row| (0..self.terminal.width).map(move |col| (col, row)))
+            .filter(|&(col, row)| self.terminal.cells[row][col] != self.recorded_cells[row][col])
+            .collect();
+        if changed_positions.is_empty() {
+            return;
+        }
+        let left = changed_positions.iter().map(|pos| pos.0).min().unwrap();
+        let right = changed_positions.iter().map(|pos| pos.0).max().unwrap();
+        let top = changed_positions.iter().map(|pos| pos.1).min().unwrap();
+        let bottom = changed_positions.iter().map(|pos| pos.1).max().unwrap();
+        let mut cells: Vec<TerminalCell> = Vec::new();
+        for row in top..=bottom {
+            cells.extend_from_slice(&self.terminal.cells[row][left..=right]);
+            self.recorded_cells[row][left..=right]
+                .copy_from_slice(&self.terminal.cells[row][left..=right]);
+        }
+        self.frames.push(RecordedFrame {
+            time,
+            left,
+            top,
+            width: right - left + 1,
+            height: bottom - top + 1,
+            cells,
+        });
+    }
+
+    // Encodes the recorded frames. Only the part of the terminal that was drawn to is part of
+    // the image, every terminal cell is a square of cell_size x cell_size pixels.
+    pub fn finish(mut self, cell_size: usize) -> std::result::Result<Vec<u8>, String> {
+        self.record_frame(self.time_last_write, true);
+        if self.frames.is_empty() {
+            return Err(String::from("Nothing was recorded."));
+        }
+        if cell_size == 0 {
+            return Err(String::from("The scale needs to be at least 1."));
+        }
+        let time_end =
+            virtual_time().max(self.frames.last().unwrap().time + GIF_FRAME_DURATION_MIN);
+
+        // The part of the terminal that was drawn to.
+        let crop_left = self.frames.iter().map(|frame| frame.left).min().unwrap();
+        let crop_top = self.frames.iter().map(|frame| frame.top).min().unwrap();
+        let crop_right = self
+            .frames
+            .iter()
+            .map(|frame| frame.left + frame.width)
+            .max()
+            .unwrap();
+        let crop_bottom = self
+            .frames
+            .iter()
+            .map(|frame| frame.top + frame.height)
+            .max()
+            .unwrap();
+        let image_width = u16::try_from((crop_right - crop_left) * cell_size)
+            .map_err(|_| String::from("The image is too large for a GIF."))?;
+        let image_height = u16::try_from((crop_bottom - crop_top) * cell_size)
+            .map_err(|_| String::from("The image is too large for a GIF."))?;
+
+        let mut palette = Palette::new();
+        for cell in self.frames.iter().flat_map(|frame| frame.cells.iter()) {
+            palette.add(cell.foreground);
+            palette.add(cell.background);
+        }
+
+        let mut gif: Vec<u8> = Vec::new();
+        {
+            let mut encoder = gif::Encoder::new(&mut gif, image_width, image_height, &palette.data)
+                .map_err(|error| format!("Cannot encode gif: {}", error))?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|error| format!("Cannot encode gif: {}", error))?;
+            for (idx, frame) in self.frames.iter().enumerate() {
+                let time_next = self
+                    .frames
+                    .get(idx + 1)
+                    .map_or(time_end, |frame_next| frame_next.time);
+                // Round the points in time (and not the durations) to avoid a drift.
+                let delay = (time_next.as_millis() / 10 - frame.time.as_millis() / 10).max(1);
+                let width = frame.width * cell_size;
+                let height = frame.height * cell_size;
+                let mut buffer: Vec<u8> = vec![0; width * height];
+                for (idx_cell, cell) in frame.cells.iter().enumerate() {
+                    let (col, row) = (idx_cell % frame.width, idx_cell / frame.width);
+                    let foreground = palette.index(cell.foreground);
+                    let background = palette.index(cell.background);
+                    for y in 0..cell_size {
+                        for x in 0..cell_size {
+                            buffer[(row * cell_size + y) * width + col * cell_size + x] =
+                                if is_foreground_pixel(cell.character, x, y, cell_size) {
+                                    foreground
+                                } else {
+                                    background
+                                };
+                        }
+                    }
+                }
+                let gif_frame = gif::Frame {
+                    delay: delay.min(u16::MAX as u128) as u16,
+                    dispose: gif::DisposalMethod::Keep,
+                    left: ((frame.left - crop_left) * cell_size) as u16,
+                    top: ((frame.top - crop_top) * cell_size) as u16,
+                    width: width as u16,
+                    height: height as u16,
+                    buffer: std::borrow::Cow::Owned(buffer),
+                    ..gif::Frame::default()
+                };
+                encoder
+                    .write_frame(&gif_frame)
+                    .map_err(|error| format!("Cannot encode gif: {}", error))?;
+            }
+        }
+        Ok(gif)
+    }
+}
+
+impl Write for GifRecorder {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        // If time passed since the last write, the current content was shown during that time.
+        let time = virtual_time();
+        if time > self.time_last_write {
+            self.record_frame(self.time_last_write, false);
+            self.time_last_write = time;
+        }
+        self.terminal.write(buf)
+    }
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// The global color table of the GIF. If there are more colors than fit into it (e.g. the
+// random colors of Kruskal's algorithm), the closest color is used.
+struct Palette {
+    data: Vec<u8>,
+    indices: HashMap<(u8, u8, u8), u8>,
+}
+
+impl Palette {
+    fn new() -> Self {
+        Palette {
+            data: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, color: (u8, u8, u8)) {
+        if self.indices.len() < GIF_PALETTE_SIZE_MAX && !self.indices.contains_key(&color) {
+            self.indices.insert(color, self.indices.len() as u8);
+            self.data.extend_from_slice(&[color.0, color.1, color.2]);
+        }
+    }
+
+    fn index(&self, color: (u8, u8, u8)) -> u8 {
+        if let Some(&index) = self.indices.get(&color) {
+            return index;
+        }
+        let distance = |other: &(u8, u8, u8)| {
+            (color.0 as i32 - other.0 as i32).pow(2)
+                + (color.1 as i32 - other.1 as i32).pow(2)
+                + (color.2 as i32 - other.2 as i32).pow(2)
+        };
+        *self
+            .indices
+            .iter()
+            .min_by_key(|(other, _)| distance(other))
+            .unwrap()
+            .1
+    }
+}
+
+// A very small "font": box-drawing characters are drawn as lines, blocks are filled and all
+// other characters are drawn as a small square in the middle of the cell.
+fn is_foreground_pixel(character: char, x: usize, y: usize, cell_size: usize) -> bool {
+    let thickness = (cell_size / 4).max(1);
+    let line_start = (cell_size - thickness) / 2;
+    let line_end = line_start + thickness;
+    let on_horizontal_line = (line_start..line_end).contains(&y);
+    let on_vertical_line = (line_start..line_end).contains(&x);
+    // (left, right, up, down)
+    let lines = match character {
+        '─' | '═' | '╾' | '╼' => Some((true, true, false, false)),
+        '│' | '║' | '╿' | '╽' => Some((false, false, true, true)),
+        '╯' | '╝' => Some((true, false, true, false)),
+        '╮' | '╗' => Some((true, false, false, true)),
+        '╰' | '╚' => Some((false, true, true, false)),
+        '╭' | '╔' => Some((false, true, false, true)),
+        '╣' => Some((true, false, true, true)),
+        '╠' => Some((false, true, true, true)),
+        _ => None,
+    };
+    match (character, lines) {
+        (' ', _) => false,
+        ('█', _) => true,
+        ('╳', _) => x.abs_diff(y) < thickness || (x + y + 1).abs_diff(cell_size) < thickness,
+        (_, Some((left, right, up, down))) => {
+            (left && on_horizontal_line && x < line_end)
+                || (right && on_horizontal_line && x >= line_start)
+                || (up && on_vertical_line && y < line_end)
+                || (down && on_vertical_line && y >= line_start)
+        }
+        (_, None) => {
+            let margin = cell_size / 4;
+            (margin..cell_size - margin).contains(&x) && (margin..cell_size - margin).contains(&y)
+        }
+    }
+}
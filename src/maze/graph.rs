@@ -0,0 +1,189 @@
+use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
+use crate::maze::maze::Maze;
+use crate::maze::path::calculate_path_cost;
+use std::collections::HashMap;
+
+// A corridor from one node to the next one.
+#[derive(Debug, Clone)]
+pub struct MazeGraphEdge {
+    pub node_to: (usize, usize),
+    // The cells of the corridor, including both nodes.
+    pub cells: Vec<(usize, usize)>,
+    // The length of the corridor weighted by the terrain (without terrain, the number of steps).
+    pub cost: usize,
+}
+
+// The maze compressed to its nodes (corners, dead ends and junctions, see is_node) and the
+// corridors between them. It is built once together with is_node (see Maze::generate_graph),
+// the start and the end position are added for every solving (see splice).
+#[derive(Debug, Clone, Default)]
+pub struct MazeGraph {
+    pub edges: HashMap<(usize, usize), Vec<MazeGraphEdge>>,
+}
+
+impl MazeGraph {
+    pub fn new(maze: &Maze) -> Self {
+        let mut edges: HashMap<(usize, usize), Vec<MazeGraphEdge>> = HashMap::new();
+        for y in 0..maze.height {
+            for x in 0..maze.width {
+                if !maze.is_accessible((x, y)) || !maze.is_node[y][x] {
+                    continue;
+                }
+                edges.insert(
+                    (x, y),
+                    walk_corridors(maze, (x, y), &|pos| maze.is_node[pos.1][pos.0]),
+                );
+            }
+        }
+        MazeGraph { edges }
+    }
+
+    // Adds the start and the end position of the maze as nodes, even if they lie in the
+    // middle of a corridor. The corridor is split there, so only the two nodes at its ends
+    // change. These get their own edges instead of copying the whole graph.
+    pub fn splice(&self, maze: &Maze) -> MazeGraphSpliced<'_> {
+        let mut spliced = MazeGraphSpliced {
+            graph: self,
+            edges: HashMap::new(),
+        };
+        for pos in [maze.pos_start, maze.pos_end] {
+            if !maze.is_accessible(pos) || spliced.is_node(pos) {
+                continue;
+            }
+            let edges = walk_corridors(maze, pos, &|pos| spliced.is_node(pos));
+            for edge in edges.iter() {
+                // The corridor of the node in the direction of pos ends at pos now.
+                let cells: Vec<(usize, usize)> = edge.cells.iter().rev().cloned().collect();
+                let edges_node = spliced
+                    .edges
+                    .entry(edge.node_to)
+                    .or_insert_with(|| self.edges[&edge.node_to].clone());
+                if let Some(edge_node) = edges_node
+                    .iter_mut()
+                    .find(|edge_node| edge_node.cells[1] == cells[1])
+                {
+                    *edge_node = MazeGraphEdge {
+                        node_to: pos,
+                        cost: calculate_path_cost(maze, &cells),
+                        cells,
+                    };
+                }
+            }
+            spliced.edges.insert(pos, edges);
+        }
+        spliced
+    }
+}
+
+// The graph of the maze including the start and the end position (see MazeGraph::splice).
+pub struct MazeGraphSpliced<'a> {
+    graph: &'a MazeGraph,
+    // The edges of the start and the end position and of the nodes next to them.
+    edges: HashMap<(usize, usize), Vec<MazeGraphEdge>>,
+}
+
+impl MazeGraphSpliced<'_> {
+    pub fn edges(&self, pos: (usize, usize)) -> &[MazeGraphEdge] {
+        self.edges
+            .get(&pos)
+            .or_else(|| self.graph.edges.get(&pos))
+            .map_or(&[], |edges| edges.as_slice())
+    }
+
+    fn is_node(&self, pos: (usize, usize)) -> bool {
+        self.edges.contains_key(&pos) || self.graph.edges.contains_key(&pos)
+    }
+}
+
+// Walks along every corridor leaving the given position until the next node.
+fn walk_corridors(
+    maze: &Maze,
+    pos_from: (usize, usize),
+    is_node: &dyn Fn((usize, usize)) -> bool,
+) -> Vec<MazeGraphEdge> {
+    let mut edges: Vec<MazeGraphEdge> = Vec::new();
+    'corridors: for direction in [
+        AbsoluteDirection::Right,
+        AbsoluteDirection::Down,
+        AbsoluteDirection::Left,
+        AbsoluteDirection::Up,
+    ] {
+        if !maze.is_accessible(direction.apply(pos_from)) {
+            continue;
+        }
+        // Walk along the corridor until the next node. Between two nodes, the corridor is
+        // straight (every curve is a node), but follow it anyway.
+        let mut cells = vec![pos_from];
+        let mut pos = pos_from;
+        let mut direction = direction;
+        loop {
+            pos = direction.apply(pos);
+            cells.push(pos);
+            if is_node(pos) {
+                break;
+            }
+            let next_direction = [
+                RelativeDirection::Forward,
+                RelativeDirection::Left,
+                RelativeDirection::Right,
+            ]
+            .iter()
+            .map(|&relative_direction| direction.add_relative_direction(relative_direction))
+            .find(|next_direction| maze.is_accessible(next_direction.apply(pos)));
+            match next_direction {
+                Some(next_direction) => direction = next_direction,
+                // A dead end that is not marked as a node (it should be), the corridor leads
+                // nowhere.
+                None => continue 'corridors,
+            }
+        }
+        edges.push(MazeGraphEdge {
+            node_to: pos,
+            cost: calculate_path_cost(maze, &cells),
+            cells,
+        });
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::{Braid, MazeGenerationAlgorithms, Terrain};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn spliced_graph_equals_the_graph_with_start_and_end_as_nodes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for algorithm in MazeGenerationAlgorithms::all() {
+            let mut maze = Maze::new(31, 21, (1, 1));
+            maze.change_size(31, 21);
+            let generator = Terrain::wrap(Braid::wrap(algorithm.to_generator(), 0.5), true);
+            maze.generate(&*generator, 3, &mut NullEventHandler);
+            for _ in 0..20 {
+                maze.set_random_start_end_position(&mut rng);
+                let graph = maze.graph.splice(&maze);
+
+                let mut maze_nodes = maze.clone();
+                for pos in [maze.pos_start, maze.pos_end] {
+                    maze_nodes.is_node[pos.1][pos.0] = true;
+                }
+                let graph_nodes = MazeGraph::new(&maze_nodes);
+                for (&pos, edges_nodes) in graph_nodes.edges.iter() {
+                    let edges = graph.edges(pos);
+                    assert_eq!(edges.len(), edges_nodes.len(), "{:?}", pos);
+                    for edge_nodes in edges_nodes {
+                        let edge = edges
+                            .iter()
+                            .find(|edge| edge.cells == edge_nodes.cells)
+                            .unwrap();
+                        assert_eq!(edge.node_to, edge_nodes.node_to);
+                        assert_eq!(edge.cost, edge_nodes.cost);
+                    }
+                }
+            }
+        }
+    }
+}
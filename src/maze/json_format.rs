@@ -0,0 +1,222 @@
+use crate::maze::maze::*;
+use crate::maze::path::get_solving_sequence;
+use serde::{Deserialize, Serialize};
+
+// The JSON representation of a maze. Positions are (x, y), the grids are indexed [y][x].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MazeJson {
+    pub width: usize,
+    pub height: usize,
+    pub pos_start: (usize, usize),
+    pub pos_end: (usize, usize),
+    pub walls: Vec<Vec<bool>>,
+    pub is_node: Vec<Vec<bool>>,
+    // The cost of walking into every field, only if the maze has terrain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub costs: Option<Vec<Vec<usize>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+// The JSON representation of the result of a solver.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SolverResultJson {
+    pub solver: String,
+    pub path: Vec<(usize, usize)>,
+    pub solving_sequence: String,
+    pub number_of_inspected_cells: usize,
+    // Only for the graph solvers (number_of_inspected_cells counts the nodes then): the cells of
+    // the corridors of the inspected nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_of_covered_cells: Option<usize>,
+    pub inspection_order: Vec<(usize, usize)>,
+}
+
+impl MazeJson {
+    pub fn from_maze(maze: &Maze) -> Self {
+        Self {
+            width: maze.width,
+            height: maze.height,
+            pos_start: maze.pos_start,
+            pos_end: maze.pos_end,
+            walls: maze
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&datum| datum == MAZE_VALUE_BLOCKED)
+                        .collect()
+                })
+                .collect(),
+            is_node: maze.is_node.clone(),
+            costs: if maze.cost_range() == (MAZE_COST_DEFAULT, MAZE_COST_DEFAULT) {
+                None
+            } else {
+                Some(maze.costs.clone())
+            },
+            generator: maze.generator.clone(),
+            seed: maze.seed,
+        }
+    }
+
+    pub fn to_maze(&self) -> Result<Maze, String> {
+        if self.width < 3 || self.height < 3 {
+            return Err(String::from(
+                "The maze needs to be at least 3x3 fields large.",
+            ));
+        }
+        for (name, grid) in [("walls", &self.walls), ("is_node", &self.is_node)] {
+            if grid.len() != self.height || grid.iter().any(|row| row.len() != self.width) {
+                return Err(format!(
+                    "The size of '{}' does not match {}x{}.",
+                    name, self.width, self.height
+                ));
+            }
+        }
+        if let Some(costs) = &self.costs {
+            if costs.len() != self.height || costs.iter().any(|row| row.len() != self.width) {
+                return Err(format!(
+                    "The size of 'costs' does not match {}x{}.",
+                    self.width, self.height
+                ));
+            }
+        }
+        let data: Vec<Vec<bool>> = self
+            .walls
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&is_wall| {
+                        if is_wall {
+                            MAZE_VALUE_BLOCKED
+                        } else {
+                            MAZE_VALUE_ACCESSIBLE
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let is_border_open = (0..self.height).any(|row| {
+            (0..self.width).any(|col| {
+                (row == 0 || row == self.height - 1 || col == 0 || col == self.width - 1)
+                    && data[row][col] == MAZE_VALUE_ACCESSIBLE
+            })
+        });
+        if is_border_open {
+            return Err(String::from("The border of the maze is open."));
+        }
+        for (name, pos) in [("pos_start", self.pos_start), ("pos_end", self.pos_end)] {
+            if pos.0 >= self.width
+                || pos.1 >= self.height
+                || data[pos.1][pos.0] == MAZE_VALUE_BLOCKED
+            {
+                return Err(format!("'{}' is not an accessible position.", name));
+            }
+        }
+        // The graph (is_node) is generated from the walls again instead of taken from the file.
+        let mut maze = Maze::from_data(data, self.pos_start, self.pos_end);
+        if let Some(costs) = &self.costs {
+            maze.costs = costs.clone();
+            // The cost of the corridors depends on the terrain.
+            maze.generate_graph();
+        }
+        maze.generator = self.generator.clone();
+        maze.seed = self.seed;
+        Ok(maze)
+    }
+}
+
+impl SolverResultJson {
+    pub fn new(
+        solver: String,
+        path: Vec<(usize, usize)>,
+        number_of_inspected_cells: usize,
+        number_of_covered_cells: Option<usize>,
+        inspection_order: Vec<(usize, usize)>,
+    ) -> Self {
+        Self {
+            solver,
+            solving_sequence: get_solving_sequence(&path).iter().collect(),
+            path,
+            number_of_inspected_cells,
+            number_of_covered_cells,
+            inspection_order,
+        }
+    }
+}
+
+pub fn solver_result_to_json(result: &SolverResultJson) -> String {
+    serde_json::to_string(result).unwrap()
+}
+
+pub fn maze_to_json(maze: &Maze) -> String {
+    serde_json::to_string(&MazeJson::from_maze(maze)).unwrap()
+}
+
+// Reads a maze from JSON (see maze_to_json). The maze needs to be surrounded by blocked fields.
+pub fn maze_from_json(json: &str) -> Result<Maze, String> {
+    serde_json::from_str::<MazeJson>(json)
+        .map_err(|error| format!("Invalid maze JSON: {}", error))?
+        .to_maze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::{Braid, MazeGenerationAlgorithms, Terrain};
+    use crate::maze::solver::MazeSolvingAlgorithms;
+
+    fn generate_maze(algorithm: &MazeGenerationAlgorithms, terrain: bool) -> Maze {
+        let mut maze = Maze::new(31, 21, (1, 1));
+        maze.change_size(31, 21);
+        let generator = Terrain::wrap(Braid::wrap(algorithm.to_generator(), 0.5), terrain);
+        maze.generate(&*generator, 3, &mut NullEventHandler);
+        maze
+    }
+
+    #[test]
+    fn maze_round_trips_through_json() {
+        for algorithm in &MazeGenerationAlgorithms::all() {
+            for terrain in [false, true] {
+                let maze = generate_maze(algorithm, terrain);
+                let json = maze_to_json(&maze);
+                let maze_read = maze_from_json(&json).unwrap();
+                assert_eq!(maze_read.data, maze.data);
+                assert_eq!(maze_read.costs, maze.costs);
+                assert_eq!(maze_read.is_node, maze.is_node);
+                assert_eq!(maze_read.pos_start, maze.pos_start);
+                assert_eq!(maze_read.pos_end, maze.pos_end);
+                assert_eq!(maze_read.generator, maze.generator);
+                assert_eq!(maze_read.seed, maze.seed);
+                assert_eq!(maze_to_json(&maze_read), json);
+            }
+        }
+    }
+
+    #[test]
+    fn graph_is_generated_instead_of_read() {
+        let maze = generate_maze(&MazeGenerationAlgorithms::all()[0], false);
+        let mut maze_json = MazeJson::from_maze(&maze);
+        maze_json.is_node = vec![vec![false; maze.width]; maze.height];
+        assert_eq!(maze_json.to_maze().unwrap().is_node, maze.is_node);
+    }
+
+    #[test]
+    fn unreachable_end_position_is_loaded_and_not_found() {
+        let maze = generate_maze(&MazeGenerationAlgorithms::all()[0], false);
+        let mut maze_json = MazeJson::from_maze(&maze);
+        let (x, y) = maze.pos_end;
+        for pos in [(x - 1, y), (x, y - 1)] {
+            maze_json.walls[pos.1][pos.0] = true;
+        }
+        let mut maze_read = maze_json.to_maze().unwrap();
+        for algorithm in MazeSolvingAlgorithms::all() {
+            let solver = algorithm.to_solver();
+            let (path, _) = maze_read.solve(&*solver, &mut NullEventHandler);
+            assert!(path.is_empty(), "{}", solver.to_string());
+        }
+    }
+}
@@ -1,16 +1,30 @@
 use crate::maze::draw::*;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::generator::MazeGenerator;
+use crate::maze::graph::MazeGraph;
 use crate::maze::solver::MazeSolver;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
 use std::io::Write;
 
-const MAZE_EDGE_LENGTH_MIN: usize = 11;
+pub const MAZE_EDGE_LENGTH_MIN: usize = 11;
 const MAZE_DEFAULT_WIDTH: usize = 21;
 const MAZE_DEFAULT_HEIGHT: usize = 21;
-const FORCE_SQUARE_MAZES: bool = true;
 
 pub const MAZE_VALUE_ACCESSIBLE: bool = false;
 pub const MAZE_VALUE_BLOCKED: bool = true;
+// The cost of walking through a field without any terrain.
+pub const MAZE_COST_DEFAULT: usize = 1;
+
+// The state of the maze before the generation algorithm starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazeInitialState {
+    // Every cell is blocked, the generator carves passages.
+    Blocked,
+    // Every cell except the border is accessible, the generator adds walls.
+    OpenWithBorder,
+}
 
 #[derive(Debug, Clone)]
 pub struct Maze {
@@ -22,8 +36,19 @@ pub struct Maze {
     pub max_height: usize,
     pub data: Vec<Vec<bool>>,
     pub is_node: Vec<Vec<bool>>,
+    pub graph: MazeGraph, // The nodes and the corridors between them (see generate_graph).
+    pub costs: Vec<Vec<usize>>, // The cost of walking into a field (e.g. mud or water).
     pub collection_position: (usize, usize), // (pos, number of mazes), pos starts at 1
     pub is_generated: bool,
+    pub seed: Option<u64>,         // The seed the maze was generated with.
+    pub generator: Option<String>, // The name of the generator the maze was generated with.
+    pub inspection_order: Vec<(usize, usize)>, // The cells inspected by the last solver (in order).
+    // The cells of the corridors the last solver walked along, if it solved the graph (its
+    // inspected cells are the nodes then).
+    pub number_of_covered_cells: Option<usize>,
+    // The start and end position of a loaded maze (e.g. a hand-drawn one), the corners can be
+    // walls there.
+    pub pos_start_end_loaded: Option<((usize, usize), (usize, usize))>,
 }
 
 impl Maze {
@@ -46,12 +71,8 @@ impl Maze {
             1 => max_height,
             _ => unreachable!(),
         };
-        let mut width = Maze::check_edge_length(MAZE_DEFAULT_WIDTH, max_width);
-        let mut height = Maze::check_edge_length(MAZE_DEFAULT_HEIGHT, max_height);
-        if FORCE_SQUARE_MAZES {
-            width = width.min(height);
-            height = width;
-        }
+        let width = Maze::check_edge_length(MAZE_DEFAULT_WIDTH, max_width);
+        let height = Maze::check_edge_length(MAZE_DEFAULT_HEIGHT, max_height);
         if collection_position.0 < 1 || collection_position.0 > collection_position.1 {
             panic!();
         }
@@ -62,13 +83,52 @@ impl Maze {
             pos_end: (width - 2, height - 2),
             max_width,
             max_height,
-            data: vec![vec![MAZE_VALUE_BLOCKED; height]; height],
+            data: vec![vec![MAZE_VALUE_BLOCKED; width]; height],
             is_node: vec![vec![false; width]; height],
+            graph: MazeGraph::default(),
+            costs: vec![vec![MAZE_COST_DEFAULT; width]; height],
             collection_position,
             is_generated: false,
+            seed: None,
+            generator: None,
+            inspection_order: Vec::new(),
+            number_of_covered_cells: None,
+            pos_start_end_loaded: None,
         }
     }
 
+    // Creates an already generated maze from the given data (e.g. read from a file).
+    // The border of the maze needs to be blocked.
+    pub fn from_data(
+        data: Vec<Vec<bool>>,
+        pos_start: (usize, usize),
+        pos_end: (usize, usize),
+    ) -> Self {
+        let height = data.len();
+        let width = data[0].len();
+        let mut maze = Maze {
+            width,
+            height,
+            pos_start,
+            pos_end,
+            max_width: width,
+            max_height: height,
+            data,
+            is_node: vec![vec![false; width]; height],
+            graph: MazeGraph::default(),
+            costs: vec![vec![MAZE_COST_DEFAULT; width]; height],
+            collection_position: (1, 1),
+            is_generated: true,
+            seed: None,
+            generator: None,
+            inspection_order: Vec::new(),
+            number_of_covered_cells: None,
+            pos_start_end_loaded: Some((pos_start, pos_end)),
+        };
+        maze.generate_graph();
+        maze
+    }
+
     fn check_edge_length(edge_length: usize, max_length: usize) -> usize {
         if edge_length < MAZE_EDGE_LENGTH_MIN {
             MAZE_EDGE_LENGTH_MIN
@@ -82,22 +142,21 @@ impl Maze {
     }
 
     pub fn change_size(&mut self, width: usize, height: usize) -> bool {
-        let mut width = Maze::check_edge_length(width, self.max_width);
-        let mut height = Maze::check_edge_length(height, self.max_height);
-        if FORCE_SQUARE_MAZES {
-            width = width.min(height);
-            height = width;
-        }
+        let width = Maze::check_edge_length(width, self.max_width);
+        let height = Maze::check_edge_length(height, self.max_height);
         if width == self.width && height == self.height {
             // Cannot make the mazer smaller / bigger.
             return false;
         }
         self.width = width;
         self.height = height;
+        self.pos_start_end_loaded = None;
         self.pos_start = (1, 1);
         self.pos_end = (width - 2, height - 2);
         self.data = vec![vec![MAZE_VALUE_BLOCKED; width]; height];
         self.is_node = vec![vec![false; width]; height];
+        self.graph = MazeGraph::default();
+        self.costs = vec![vec![MAZE_COST_DEFAULT; width]; height];
         return true;
     }
 
@@ -115,19 +174,66 @@ impl Maze {
         self.data[pos.1][pos.0] == MAZE_VALUE_BLOCKED
     }
 
+    pub fn cost(&self, pos: (usize, usize)) -> usize {
+        self.costs[pos.1][pos.0]
+    }
+
+    // The lowest and the highest cost of the accessible fields.
+    pub fn cost_range(&self) -> (usize, usize) {
+        let costs = self
+            .data
+            .iter()
+            .zip(self.costs.iter())
+            .flat_map(|(data_row, costs_row)| {
+                data_row
+                    .iter()
+                    .zip(costs_row.iter())
+                    .filter(|&(&datum, _)| datum == MAZE_VALUE_ACCESSIBLE)
+                    .map(|(_, &cost)| cost)
+            });
+        costs
+            .fold(None, |range: Option<(usize, usize)>, cost| match range {
+                Some((min, max)) => Some((min.min(cost), max.max(cost))),
+                None => Some((cost, cost)),
+            })
+            .unwrap_or((MAZE_COST_DEFAULT, MAZE_COST_DEFAULT))
+    }
+
     pub fn reset(&mut self) {
+        self.pos_start_end_loaded = None;
         for row in 0..self.height {
             for col in 0..self.width {
                 self.data[row][col] = MAZE_VALUE_BLOCKED;
                 self.is_node[row][col] = false;
+                self.costs[row][col] = MAZE_COST_DEFAULT;
+            }
+        }
+        self.graph = MazeGraph::default();
+        self.is_generated = false;
+    }
+
+    pub fn reset_to_open_field(&mut self) {
+        self.pos_start_end_loaded = None;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.data[row][col] =
+                    if row == 0 || row == self.height - 1 || col == 0 || col == self.width - 1 {
+                        MAZE_VALUE_BLOCKED
+                    } else {
+                        MAZE_VALUE_ACCESSIBLE
+                    };
+                self.is_node[row][col] = false;
+                self.costs[row][col] = MAZE_COST_DEFAULT;
             }
         }
+        self.graph = MazeGraph::default();
         self.is_generated = false;
     }
 
     pub fn reset_start_end_position(&mut self) {
-        self.pos_start = (1, 1);
-        self.pos_end = (self.width - 2, self.height - 2);
+        (self.pos_start, self.pos_end) = self
+            .pos_start_end_loaded
+            .unwrap_or(((1, 1), (self.width - 2, self.height - 2)));
     }
 
     pub fn set_start_end_position(
@@ -143,7 +249,7 @@ impl Maze {
         }
     }
 
-    fn get_random_accessible_position(&self) -> (usize, usize) {
+    fn get_random_accessible_position(&self, rng: &mut dyn RngCore) -> (usize, usize) {
         self.data
             .iter()
             .enumerate()
@@ -154,32 +260,38 @@ impl Maze {
                     .map(move |(x, _)| (x, y))
             })
             .collect::<Vec<(usize, usize)>>()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .cloned()
             .unwrap()
     }
 
-    pub fn set_random_start_end_position(&mut self) {
+    pub fn set_random_start_end_position(&mut self, rng: &mut dyn RngCore) {
         // The two positions need to be accessible and also not the same.
-        self.pos_start = self.get_random_accessible_position();
-        self.pos_end = self.get_random_accessible_position();
+        self.pos_start = self.get_random_accessible_position(rng);
+        self.pos_end = self.get_random_accessible_position(rng);
         while self.pos_end == self.pos_start {
-            self.pos_end = self.get_random_accessible_position();
+            self.pos_end = self.get_random_accessible_position(rng);
         }
     }
 
     pub fn generate(
         &mut self,
         generator: &dyn MazeGenerator,
-        screen: &mut dyn Write,
-        animate: bool,
+        seed: u64,
+        events: &mut dyn MazeEventHandler,
     ) {
         // Draw the maze as empty as it is and draw it empty.
-        self.reset();
+        match generator.initial_state() {
+            MazeInitialState::Blocked => self.reset(),
+            MazeInitialState::OpenWithBorder => self.reset_to_open_field(),
+        }
         self.reset_start_end_position();
-        self.draw(screen, false, false, false, false, false);
-        // Generate the maze.
-        generator.generate(self, screen, animate);
+        events.handle(self, MazeEvent::MazeChanged);
+        // Generate the maze. The same seed always leads to the same maze.
+        self.seed = Some(seed);
+        self.generator = Some(generator.to_string());
+        let mut rng = StdRng::seed_from_u64(seed);
+        generator.generate(self, &mut rng, events);
         // Generate the graph once.
         self.generate_graph();
         self.is_generated = true;
@@ -188,13 +300,14 @@ impl Maze {
     pub fn solve(
         &mut self,
         solver: &dyn MazeSolver,
-        screen: &mut dyn Write,
-        animate: bool,
+        events: &mut dyn MazeEventHandler,
     ) -> (Vec<(usize, usize)>, usize) {
         if self.is_generated {
             // Draw the maze again (this may delete the path from the previous solving).
-            self.draw(screen, false, false, false, false, false);
-            solver.solve(self, screen, animate)
+            events.handle(self, MazeEvent::MazeChanged);
+            self.inspection_order.clear();
+            self.number_of_covered_cells = None;
+            solver.solve(self, events)
         } else {
             (Vec::new(), 0)
         }
@@ -329,5 +442,8 @@ impl Maze {
                 }
             }
         }
+        // The corridors between the nodes, so the graph solvers do not need to walk through
+        // the maze every time.
+        self.graph = MazeGraph::new(self);
     }
 }
@@ -39,13 +39,19 @@ impl MazeCollection {
     pub fn generate(
         &mut self,
         generator: &dyn MazeGenerator,
+        seed: u64,
         screen: &mut dyn Write,
         animate: bool,
     ) {
         self.mazes.iter_mut().for_each(|maze| maze.erase(screen));
-        self.mazes
-            .iter_mut()
-            .for_each(|maze| maze.generate(generator, screen, animate));
+        // Every maze gets its own seed (derived from the given one), otherwise all mazes would be equal.
+        self.mazes.iter_mut().enumerate().for_each(|(idx, maze)| {
+            maze.generate(
+                generator,
+                seed.wrapping_add(idx as u64),
+                &mut TerminalRenderer::new(screen, animate),
+            )
+        });
     }
 
     pub fn solve(
@@ -75,7 +81,7 @@ impl MazeCollection {
             }
             // Solve the maze.
             let (mut sub_path, sub_inspected_cells) =
-                self.mazes[idx].solve(solver, screen, animate);
+                self.mazes[idx].solve(solver, &mut TerminalRenderer::new(screen, animate));
             // Wait a little bit and then redraw the maze.
             if animate {
                 delay(Delay::VeryLong);
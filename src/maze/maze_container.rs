@@ -1,3 +1,4 @@
+use crate::maze::draw::TerminalRenderer;
 use crate::maze::generator::MazeGenerator;
 use crate::maze::maze::Maze;
 use crate::maze::maze_collection::MazeCollection;
@@ -5,7 +6,7 @@ use crate::maze::solver::MazeSolver;
 use std::io::Write;
 
 pub enum MazeContainer {
-    SingleMaze(Maze),
+    SingleMaze(Box<Maze>),
     MultipleMazes(MazeCollection),
 }
 
@@ -21,13 +22,14 @@ impl MazeContainer {
     pub fn generate(
         &mut self,
         generator: &dyn MazeGenerator,
+        seed: u64,
         screen: &mut dyn Write,
         animate: bool,
     ) {
         if let MazeContainer::SingleMaze(ref mut maze) = self {
-            maze.generate(generator, screen, animate);
+            maze.generate(generator, seed, &mut TerminalRenderer::new(screen, animate));
         } else if let MazeContainer::MultipleMazes(ref mut maze_collection) = self {
-            maze_collection.generate(generator, screen, animate);
+            maze_collection.generate(generator, seed, screen, animate);
         }
     }
 
@@ -38,7 +40,7 @@ impl MazeContainer {
         animate: bool,
     ) -> (Vec<(usize, usize)>, usize) {
         if let MazeContainer::SingleMaze(ref mut maze) = self {
-            maze.solve(solver, screen, animate)
+            maze.solve(solver, &mut TerminalRenderer::new(screen, animate))
         } else if let MazeContainer::MultipleMazes(ref mut maze_collection) = self {
             maze_collection.solve(solver, screen, animate)
         } else {
@@ -1,10 +1,20 @@
 pub mod animation;
+pub mod asciicast;
 pub mod benchmark;
 pub mod direction;
 pub mod draw;
+pub mod event;
 pub mod generator;
+pub mod gif;
+pub mod graph;
+pub mod json_format;
 pub mod maze;
 pub mod maze_collection;
 pub mod maze_container;
 pub mod path;
+pub mod png;
 pub mod solver;
+pub mod step_debugger;
+pub mod svg;
+pub mod text_format;
+pub mod virtual_terminal;
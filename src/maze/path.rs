@@ -1,5 +1,6 @@
 use crate::maze::direction::AbsoluteDirection;
 use crate::maze::maze::*;
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq)]
 enum PathOrientation {
@@ -101,3 +102,86 @@ pub fn apply_solving_sequence(
 pub fn calculate_manhattan_distance(pos1: (usize, usize), pos2: (usize, usize)) -> usize {
     pos1.0.abs_diff(pos2.0) + pos1.1.abs_diff(pos2.1)
 }
+
+// The cost of walking along the path (the start position is not entered, so it costs nothing).
+pub fn calculate_path_cost(maze: &Maze, path: &[(usize, usize)]) -> usize {
+    path.iter().skip(1).map(|&pos| maze.cost(pos)).sum()
+}
+
+// Joins the path to a cell with the path from this cell on (e.g. where the two searches of a
+// bidirectional solver meet).
+pub fn join_paths(path_to: &[(usize, usize)], path_from: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    path_to
+        .iter()
+        .chain(path_from.iter().skip(1))
+        .cloned()
+        .collect()
+}
+
+// Removes the loops from a walked path: walking through a cell again cuts off everything that
+// was walked since the first time, so only the way from the first to the last cell is left.
+pub fn erase_loops(path: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut path_erased: Vec<(usize, usize)> = Vec::new();
+    let mut indices: HashMap<(usize, usize), usize> = HashMap::new();
+    for &pos in path {
+        match indices.get(&pos) {
+            Some(&idx) => {
+                for pos_removed in path_erased.drain(idx + 1..) {
+                    indices.remove(&pos_removed);
+                }
+            }
+            None => {
+                indices.insert(pos, path_erased.len());
+                path_erased.push(pos);
+            }
+        }
+    }
+    path_erased
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_paths_keeps_the_meeting_cell_once() {
+        let path_to = vec![(1, 1), (2, 1), (3, 1)];
+        let path_from = vec![(3, 1), (3, 2), (3, 3)];
+        assert_eq!(
+            join_paths(&path_to, &path_from),
+            vec![(1, 1), (2, 1), (3, 1), (3, 2), (3, 3)]
+        );
+        // The searches can also meet at the start or the end position.
+        assert_eq!(join_paths(&[(1, 1)], &path_to), path_to);
+        assert_eq!(join_paths(&path_to, &[(3, 1)]), path_to);
+    }
+
+    #[test]
+    fn erase_loops_cuts_off_dead_ends_and_circles() {
+        // Into a dead end and out again.
+        let path = vec![(1, 1), (2, 1), (3, 1), (2, 1), (2, 2), (2, 3)];
+        assert_eq!(erase_loops(&path), vec![(1, 1), (2, 1), (2, 2), (2, 3)]);
+        // Around a block and back to the start, then on.
+        let path = vec![
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (2, 3),
+            (1, 3),
+            (1, 2),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (1, 4),
+        ];
+        assert_eq!(erase_loops(&path), vec![(1, 1), (1, 2), (1, 3), (1, 4)]);
+        // Loops within loops.
+        let path = vec![(1, 1), (2, 1), (3, 1), (4, 1), (3, 1), (2, 1), (2, 2)];
+        assert_eq!(erase_loops(&path), vec![(1, 1), (2, 1), (2, 2)]);
+        // Without loops, the path stays as it is.
+        let path = vec![(1, 1), (1, 2), (2, 2)];
+        assert_eq!(erase_loops(&path), path);
+    }
+}
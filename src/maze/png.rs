@@ -0,0 +1,76 @@
+use crate::maze::draw::CellColorType;
+use crate::maze::maze::*;
+
+const PNG_COLOR_WALL: (u8, u8, u8) = (0, 0, 0);
+const PNG_COLOR_BACKGROUND: (u8, u8, u8) = (255, 255, 255);
+pub const PNG_SCALE_DEFAULT: usize = 8;
+
+// Renders the maze as PNG with every field of the maze being a square of scale x scale pixels.
+// Optionally, the inspected cells and the path are drawn in the colors of the terminal ui.
+pub fn maze_to_png(
+    maze: &Maze,
+    path: Option<&[(usize, usize)]>,
+    inspection_order: Option<&[(usize, usize)]>,
+    scale: usize,
+) -> Result<Vec<u8>, String> {
+    if scale == 0 {
+        return Err(String::from("The scale needs to be at least 1."));
+    }
+
+    // The color of every field of the maze. Later layers overwrite earlier ones.
+    let mut fields: Vec<Vec<(u8, u8, u8)>> = maze
+        .data
+        .iter()
+        .map(|data_row| {
+            data_row
+                .iter()
+                .map(|&datum| {
+                    if datum == MAZE_VALUE_BLOCKED {
+                        PNG_COLOR_WALL
+                    } else {
+                        PNG_COLOR_BACKGROUND
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for &(x, y) in inspection_order.unwrap_or_default() {
+        fields[y][x] = CellColorType::InspectedCell.to_rgb();
+    }
+    for &(x, y) in path.unwrap_or_default() {
+        fields[y][x] = CellColorType::Path.to_rgb();
+    }
+    fields[maze.pos_start.1][maze.pos_start.0] = CellColorType::Path.to_rgb();
+    fields[maze.pos_end.1][maze.pos_end.0] = CellColorType::CurrentCell.to_rgb();
+
+    // Scale the fields up to pixels.
+    let mut pixels: Vec<u8> = Vec::with_capacity(maze.width * maze.height * scale * scale * 3);
+    for row in fields.iter() {
+        let pixel_row: Vec<u8> = row
+            .iter()
+            .flat_map(|&(r, g, b)| [r, g, b].repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&pixel_row);
+        }
+    }
+
+    let mut png: Vec<u8> = Vec::new();
+    let mut encoder = png::Encoder::new(
+        &mut png,
+        (maze.width * scale) as u32,
+        (maze.height * scale) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|error| format!("Cannot encode png: {}", error))?;
+    writer
+        .write_image_data(&pixels)
+        .map_err(|error| format!("Cannot encode png: {}", error))?;
+    writer
+        .finish()
+        .map_err(|error| format!("Cannot encode png: {}", error))?;
+    Ok(png)
+}
@@ -1,6 +1,6 @@
-use crate::maze::animation::delay;
 use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
-use crate::maze::draw::{draw_path, highlight_cell, CellColorType};
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::maze::Maze;
 use crate::maze::path::calculate_manhattan_distance;
 use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
@@ -12,20 +12,25 @@ impl MazeSolver for AStar {
     fn solve(
         &self,
         maze: &mut Maze,
-        screen: &mut dyn std::io::Write,
-        animate: bool,
+        events: &mut dyn MazeEventHandler,
     ) -> (Vec<(usize, usize)>, usize) {
+        // g is the cost of the path so far. Every step costs at least the cheapest field, so
+        // the heuristic never overestimates the remaining cost (admissible).
+        let (cost_min, _) = maze.cost_range();
+        // The queue holds (f, position, direction, g, path), so the lowest f comes first.
         let mut queue: BTreeSet<(
             usize,
             (usize, usize),
             Option<AbsoluteDirection>,
+            usize,
             Vec<(usize, usize)>,
         )> = BTreeSet::new();
         // Add the start position.
         queue.insert((
-            calculate_manhattan_distance(maze.pos_start, maze.pos_end),
+            calculate_manhattan_distance(maze.pos_start, maze.pos_end) * cost_min,
             maze.pos_start,
             None,
+            0,
             vec![maze.pos_start],
         ));
 
@@ -33,17 +38,25 @@ impl MazeSolver for AStar {
         let mut inspected_cells: HashSet<(usize, usize)> = HashSet::new();
 
         while !queue.is_empty() {
-            let (_, pos, direction, path) = queue.pop_first().unwrap();
-            inspected_cells.insert(pos);
-            highlight_cell(screen, maze, pos, CellColorType::CurrentCell);
+            let (_, pos, direction, cost, path) = queue.pop_first().unwrap();
+            // In a braided maze, a cell can be reached on several ways. Only inspect it once.
+            if !inspected_cells.insert(pos) {
+                continue;
+            }
+            maze.inspection_order.push(pos);
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
             if pos == maze.pos_end {
-                draw_path(screen, maze, path.clone(), Some(CellColorType::Path));
+                events.handle(maze, MazeEvent::PathFound { path: path.clone() });
                 return (path, inspected_cells.len());
             }
-            if animate {
-                delay(SOLVING_DELAY);
-            }
-            highlight_cell(screen, maze, pos, CellColorType::InspectedCell);
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
             // From your current direction, first look right, forward and then left.
             // Never go back.
             let possible_directions: Vec<AbsoluteDirection> = if let Some(direction) = direction {
@@ -66,17 +79,34 @@ impl MazeSolver for AStar {
                 if maze.is_accessible(pos_next) {
                     let mut path_next = path.clone();
                     path_next.push(pos_next.clone());
+                    let cost_next = cost + maze.cost(pos_next);
                     queue.insert((
-                        (path_next.len() - 1)
-                            + calculate_manhattan_distance(pos_next, maze.pos_end),
+                        cost_next + calculate_manhattan_distance(pos_next, maze.pos_end) * cost_min,
                         pos_next,
                         Some(*next_direction),
+                        cost_next,
                         path_next,
                     ));
                 }
             }
+            if events.animate() {
+                let frontier = queue
+                    .iter()
+                    .map(|(score, pos, _, _, _)| (*pos, *score))
+                    .collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
         }
-        panic!()
+        // The end position is not reachable.
+        (Vec::new(), inspected_cells.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "f = g + h"
     }
 
     fn to_string(&self) -> String {
@@ -0,0 +1,172 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::path::{calculate_manhattan_distance, join_paths};
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+pub struct BidirectionalAStar;
+
+impl MazeSolver for BidirectionalAStar {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Run two A* searches at once, one from the start position towards the end position
+            and one from the end position towards the start position. They take turns, one
+            cell at a time.
+        2. Both searches remember the cheapest path to every cell they reached.
+        3. If a search reaches a cell the other search reached already, the frontiers meet.
+            The path through this cell is a candidate, keep the cheapest one.
+        4. Stop as soon as one of the searches cannot find a cheaper path: the lowest f of its
+            queue is at least the cost of the cheapest candidate. As the heuristic never
+            overestimates, every path through the cells left in its queue costs at least f.
+        */
+        let (cost_min, _) = maze.cost_range();
+        // Index 0 is the search from the start position, index 1 the one from the end position.
+        let targets = [maze.pos_end, maze.pos_start];
+        let mut queues: [BTreeSet<(usize, (usize, usize))>; 2] = [
+            BTreeSet::from([(
+                calculate_manhattan_distance(maze.pos_start, targets[0]) * cost_min,
+                maze.pos_start,
+            )]),
+            BTreeSet::from([(
+                calculate_manhattan_distance(maze.pos_end, targets[1]) * cost_min,
+                maze.pos_end,
+            )]),
+        ];
+        // The cheapest known (cost, path) to every reached cell. The paths always lead from the
+        // start to the end position, so the paths of the search from the end position begin at
+        // the reached cell (and their cost does not include it).
+        let mut reached = [
+            HashMap::from([(maze.pos_start, (0, vec![maze.pos_start]))]),
+            HashMap::from([(maze.pos_end, (0, vec![maze.pos_end]))]),
+        ];
+        let highlights = [
+            CellColorType::InspectedCell,
+            CellColorType::InspectedCellFromEnd,
+        ];
+        let mut path_cheapest: Option<(usize, Vec<(usize, usize)>)> =
+            if maze.pos_start == maze.pos_end {
+                Some((0, vec![maze.pos_start]))
+            } else {
+                None
+            };
+
+        // Count the inspected cells.
+        let mut inspected_cells: [HashSet<(usize, usize)>; 2] = [HashSet::new(), HashSet::new()];
+
+        let mut side = 0;
+        loop {
+            match (queues[0].first(), queues[1].first(), &path_cheapest) {
+                (Some((score_start, _)), Some((score_end, _)), Some((cost, _)))
+                    if score_start >= cost || score_end >= cost =>
+                {
+                    break
+                }
+                (Some(_), Some(_), _) => {}
+                // One of the searches has no cells left.
+                _ => break,
+            }
+
+            let (_, pos) = queues[side].pop_first().unwrap();
+            // The cell was reached cheaper later and is already inspected.
+            if !inspected_cells[side].insert(pos) {
+                continue;
+            }
+            let (cost, path) = reached[side][&pos].clone();
+            if !inspected_cells[1 - side].contains(&pos) {
+                maze.inspection_order.push(pos);
+            }
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: highlights[side],
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+
+            for next_direction in [
+                AbsoluteDirection::Right,
+                AbsoluteDirection::Down,
+                AbsoluteDirection::Left,
+                AbsoluteDirection::Up,
+            ] {
+                let pos_next = next_direction.apply(pos);
+                if !maze.is_accessible(pos_next) || inspected_cells[side].contains(&pos_next) {
+                    continue;
+                }
+                let (path_next, cost_next) = if side == 0 {
+                    (
+                        join_paths(&path, &[pos, pos_next]),
+                        cost + maze.cost(pos_next),
+                    )
+                } else {
+                    (join_paths(&[pos_next, pos], &path), cost + maze.cost(pos))
+                };
+                if let Some((cost_known, _)) = reached[side].get(&pos_next) {
+                    if *cost_known <= cost_next {
+                        continue;
+                    }
+                }
+                // The frontiers meet.
+                if let Some((cost_other, path_other)) = reached[1 - side].get(&pos_next) {
+                    let path_joined = if side == 0 {
+                        join_paths(&path_next, path_other)
+                    } else {
+                        join_paths(path_other, &path_next)
+                    };
+                    let cost_joined = cost_next + cost_other;
+                    if path_cheapest
+                        .as_ref()
+                        .is_none_or(|(cost, _)| cost_joined < *cost)
+                    {
+                        path_cheapest = Some((cost_joined, path_joined));
+                    }
+                }
+                reached[side].insert(pos_next, (cost_next, path_next));
+                queues[side].insert((
+                    cost_next + calculate_manhattan_distance(pos_next, targets[side]) * cost_min,
+                    pos_next,
+                ));
+            }
+            if events.animate() {
+                let frontier = queues
+                    .iter()
+                    .flat_map(|queue| queue.iter().map(|(score, pos)| (*pos, *score)))
+                    .collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
+            side = 1 - side;
+        }
+
+        // The searches did not meet, the end position is not reachable.
+        let Some((_, path)) = path_cheapest else {
+            return (
+                Vec::new(),
+                inspected_cells[0].union(&inspected_cells[1]).count(),
+            );
+        };
+        events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+        (path, inspected_cells[0].union(&inspected_cells[1]).count())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "f = g + h"
+    }
+
+    fn to_string(&self) -> String {
+        String::from("bidirectional A*")
+    }
+}
@@ -0,0 +1,151 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::path::join_paths;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub struct BidirectionalBreadthFirstSearch;
+
+impl MazeSolver for BidirectionalBreadthFirstSearch {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Run two breadth first searches at once, one from the start and one from the end
+            position. They take turns, one cell at a time.
+        2. Both searches remember the shortest path to every cell they reached.
+        3. If a search reaches a cell the other search reached already, the frontiers meet.
+            The path through this cell is a candidate, keep the shortest one.
+        4. Stop as soon as no shorter path is possible: the paths to the next cells of both
+            searches are together at least as long as the shortest candidate.
+        */
+        // Index 0 is the search from the start position, index 1 the one from the end position.
+        let mut queues: [VecDeque<(usize, usize)>; 2] = [
+            VecDeque::from([maze.pos_start]),
+            VecDeque::from([maze.pos_end]),
+        ];
+        // The paths always lead from the start to the end position, so the paths of the
+        // search from the end position begin at the reached cell.
+        let mut reached = [
+            HashMap::from([(maze.pos_start, vec![maze.pos_start])]),
+            HashMap::from([(maze.pos_end, vec![maze.pos_end])]),
+        ];
+        let highlights = [
+            CellColorType::InspectedCell,
+            CellColorType::InspectedCellFromEnd,
+        ];
+        let mut path_shortest: Option<Vec<(usize, usize)>> = if maze.pos_start == maze.pos_end {
+            Some(vec![maze.pos_start])
+        } else {
+            None
+        };
+
+        // Count the inspected cells.
+        let mut inspected_cells: [HashSet<(usize, usize)>; 2] = [HashSet::new(), HashSet::new()];
+
+        let mut side = 0;
+        loop {
+            let lengths_next: Vec<Option<usize>> = (0..2)
+                .map(|idx| queues[idx].front().map(|pos| reached[idx][pos].len() - 1))
+                .collect();
+            match (lengths_next[0], lengths_next[1], &path_shortest) {
+                (Some(length_start), Some(length_end), Some(path))
+                    if length_start + length_end >= path.len() - 1 =>
+                {
+                    break
+                }
+                (Some(_), Some(_), _) => {}
+                // One of the searches has no cells left.
+                _ => break,
+            }
+
+            let pos = queues[side].pop_front().unwrap();
+            let path = reached[side][&pos].clone();
+            inspected_cells[side].insert(pos);
+            if !inspected_cells[1 - side].contains(&pos) {
+                maze.inspection_order.push(pos);
+            }
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: highlights[side],
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+
+            for next_direction in [
+                AbsoluteDirection::Right,
+                AbsoluteDirection::Down,
+                AbsoluteDirection::Left,
+                AbsoluteDirection::Up,
+            ] {
+                let pos_next = next_direction.apply(pos);
+                if !maze.is_accessible(pos_next) || reached[side].contains_key(&pos_next) {
+                    continue;
+                }
+                let path_next = if side == 0 {
+                    join_paths(&path, &[pos, pos_next])
+                } else {
+                    join_paths(&[pos_next, pos], &path)
+                };
+                // The frontiers meet.
+                if let Some(path_other) = reached[1 - side].get(&pos_next) {
+                    let path_joined = if side == 0 {
+                        join_paths(&path_next, path_other)
+                    } else {
+                        join_paths(path_other, &path_next)
+                    };
+                    if path_shortest
+                        .as_ref()
+                        .is_none_or(|path| path_joined.len() < path.len())
+                    {
+                        path_shortest = Some(path_joined);
+                    }
+                }
+                reached[side].insert(pos_next, path_next);
+                queues[side].push_back(pos_next);
+            }
+            if events.animate() {
+                let frontier = queues
+                    .iter()
+                    .zip(reached.iter())
+                    .flat_map(|(queue, reached)| {
+                        queue.iter().map(move |pos| (*pos, reached[pos].len() - 1))
+                    })
+                    .collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
+            side = 1 - side;
+        }
+
+        // The searches did not meet, the end position is not reachable.
+        let Some(path) = path_shortest else {
+            return (
+                Vec::new(),
+                inspected_cells[0].union(&inspected_cells[1]).count(),
+            );
+        };
+        events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+        (path, inspected_cells[0].union(&inspected_cells[1]).count())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "g"
+    }
+
+    fn to_string(&self) -> String {
+        String::from("bidirectional breadth-first search")
+    }
+}
@@ -1,6 +1,6 @@
-use crate::maze::animation::delay;
 use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
-use crate::maze::draw::{draw_path, highlight_cell, CellColorType};
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::maze::Maze;
 use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
 use std::collections::{HashSet, VecDeque};
@@ -11,8 +11,7 @@ impl MazeSolver for BreadthFirstSearch {
     fn solve(
         &self,
         maze: &mut Maze,
-        screen: &mut dyn std::io::Write,
-        animate: bool,
+        events: &mut dyn MazeEventHandler,
     ) -> (Vec<(usize, usize)>, usize) {
         let mut queue: VecDeque<(
             (usize, usize),
@@ -27,16 +26,24 @@ impl MazeSolver for BreadthFirstSearch {
 
         while !queue.is_empty() {
             let (pos, direction, path) = queue.pop_front().unwrap();
-            inspected_cells.insert(pos);
-            highlight_cell(screen, maze, pos, CellColorType::CurrentCell);
+            // In a braided maze, a cell can be reached on several ways. Only inspect it once.
+            if !inspected_cells.insert(pos) {
+                continue;
+            }
+            maze.inspection_order.push(pos);
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
             if pos == maze.pos_end {
-                draw_path(screen, maze, path.clone(), Some(CellColorType::Path));
+                events.handle(maze, MazeEvent::PathFound { path: path.clone() });
                 return (path, inspected_cells.len());
             }
-            if animate {
-                delay(SOLVING_DELAY);
-            }
-            highlight_cell(screen, maze, pos, CellColorType::InspectedCell);
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
             // From your current direction, first look right, forward and then left.
             // Never go back.
             let possible_directions: Vec<AbsoluteDirection> = if let Some(direction) = direction {
@@ -61,8 +68,24 @@ impl MazeSolver for BreadthFirstSearch {
                     queue.push_back((pos_next, Some(*next_direction), path_next));
                 }
             }
+            if events.animate() {
+                let frontier = queue
+                    .iter()
+                    .map(|(pos, _, path)| (*pos, path.len() - 1))
+                    .collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
         }
-        panic!()
+        // The end position is not reachable.
+        (Vec::new(), inspected_cells.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "g"
     }
 
     fn to_string(&self) -> String {
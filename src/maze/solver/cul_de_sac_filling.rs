@@ -0,0 +1,131 @@
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::solver::dead_end_filling::{
+    fill_dead_ends, get_open_cells, get_open_neighbors, walk_through_open_cells,
+};
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+
+pub struct CulDeSacFilling;
+
+impl MazeSolver for CulDeSacFilling {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Fill the dead ends (see dead-end filling).
+        2. In a braided maze, there are loops that are attached to the rest of the maze by a
+            single cell (cul-de-sacs). The path never leads through them. Fill everything that
+            hangs at a single cell and contains neither the start nor the end position.
+        3. Walk from the start to the end position through the open cells that are left.
+        The filled cells count as inspected.
+        */
+        let mut open = get_open_cells(maze);
+        let filled_cells = fill_dead_ends(maze, &mut open, events);
+        maze.inspection_order.extend(filled_cells);
+        let filled_cells = fill_cul_de_sacs(maze, &mut open, events);
+        maze.inspection_order.extend(filled_cells);
+        let path = walk_through_open_cells(maze, &open, events);
+        (path, maze.inspection_order.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn to_string(&self) -> String {
+        String::from("cul-de-sac filling")
+    }
+}
+
+// Fills the parts of the maze that hang at a single cell and contain neither the start nor
+// the end position. Returns the filled cells (in order).
+fn fill_cul_de_sacs(
+    maze: &Maze,
+    open: &mut [Vec<bool>],
+    events: &mut dyn MazeEventHandler,
+) -> Vec<(usize, usize)> {
+    /*
+    Tarjan's algorithm for articulation points:
+
+    1. Walk depth first from the start position and number the cells in the order they are
+        discovered. The cells discovered from a cell form its subtree, their numbers follow
+        the number of the cell without gaps.
+    2. low is the smallest number that can be reached from the subtree of a cell by taking a
+        single step back to an already discovered cell (closing a loop).
+    3. If no cell in the subtree of a cell can reach a cell that was discovered before its
+        parent, the subtree hangs at the parent. If it does not contain the end position,
+        it is a cul-de-sac.
+    */
+    let mut discovery: Vec<Vec<Option<usize>>> = vec![vec![None; maze.width]; maze.height];
+    let mut low: Vec<Vec<usize>> = vec![vec![0; maze.width]; maze.height];
+    let mut parent: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; maze.width]; maze.height];
+    let mut subtree_size: Vec<Vec<usize>> = vec![vec![1; maze.width]; maze.height];
+    let mut discovery_order: Vec<(usize, usize)> = Vec::new();
+    let mut cul_de_sacs: Vec<(usize, usize)> = Vec::new();
+
+    // The cells that are walked through together with the neighbors that are not visited yet.
+    let mut stack = vec![(maze.pos_start, get_open_neighbors(open, maze.pos_start))];
+    discovery[maze.pos_start.1][maze.pos_start.0] = Some(0);
+    discovery_order.push(maze.pos_start);
+    while let Some((pos, neighbors)) = stack.last_mut() {
+        let pos = *pos;
+        match neighbors.pop() {
+            Some(pos_next) => match discovery[pos_next.1][pos_next.0] {
+                None => {
+                    discovery[pos_next.1][pos_next.0] = Some(discovery_order.len());
+                    low[pos_next.1][pos_next.0] = discovery_order.len();
+                    parent[pos_next.1][pos_next.0] = Some(pos);
+                    discovery_order.push(pos_next);
+                    stack.push((pos_next, get_open_neighbors(open, pos_next)));
+                }
+                // A loop is closed.
+                Some(number) if parent[pos.1][pos.0] != Some(pos_next) => {
+                    low[pos.1][pos.0] = low[pos.1][pos.0].min(number);
+                }
+                Some(_) => {}
+            },
+            None => {
+                stack.pop();
+                if let Some(pos_parent) = parent[pos.1][pos.0] {
+                    low[pos_parent.1][pos_parent.0] =
+                        low[pos_parent.1][pos_parent.0].min(low[pos.1][pos.0]);
+                    subtree_size[pos_parent.1][pos_parent.0] += subtree_size[pos.1][pos.0];
+                    let number = discovery[pos.1][pos.0].unwrap();
+                    let contains_end =
+                        discovery[maze.pos_end.1][maze.pos_end.0].is_some_and(|number_end| {
+                            (number..number + subtree_size[pos.1][pos.0]).contains(&number_end)
+                        });
+                    if low[pos.1][pos.0] >= discovery[pos_parent.1][pos_parent.0].unwrap()
+                        && !contains_end
+                    {
+                        cul_de_sacs.push(pos);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut filled_cells: Vec<(usize, usize)> = Vec::new();
+    for pos in cul_de_sacs {
+        let number = discovery[pos.1][pos.0].unwrap();
+        // The deepest cells first, so the walls close in towards the entrance.
+        for &pos_subtree in discovery_order[number..number + subtree_size[pos.1][pos.0]]
+            .iter()
+            .rev()
+        {
+            if !open[pos_subtree.1][pos_subtree.0] {
+                // Part of a smaller cul-de-sac inside this one.
+                continue;
+            }
+            open[pos_subtree.1][pos_subtree.0] = false;
+            filled_cells.push(pos_subtree);
+            events.handle(maze, MazeEvent::WallAdded { pos: pos_subtree });
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+        }
+    }
+    filled_cells
+}
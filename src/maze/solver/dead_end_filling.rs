@@ -0,0 +1,135 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::*;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{HashSet, VecDeque};
+
+pub struct DeadEndFilling;
+
+impl MazeSolver for DeadEndFilling {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Look at the whole maze instead of walking through it. Every open cell with only one
+            open neighbor (three walls around it) is a dead end, except the start and the end
+            position. Fill it with a wall.
+        2. Filling a dead end can turn its neighbor into a new dead end, fill it as well.
+            Repeat until there are no dead ends left.
+        3. In a perfect maze, only the path is left open. In a braided maze, the loops are left
+            as well, so walk from the start to the end position through the open cells.
+        The filled cells count as inspected.
+        */
+        let mut open = get_open_cells(maze);
+        let filled_cells = fill_dead_ends(maze, &mut open, events);
+        maze.inspection_order.extend(filled_cells);
+        let path = walk_through_open_cells(maze, &open, events);
+        (path, maze.inspection_order.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn to_string(&self) -> String {
+        String::from("dead-end filling")
+    }
+}
+
+// Which fields of the maze are open (accessible), the filling solvers work on a copy.
+pub fn get_open_cells(maze: &Maze) -> Vec<Vec<bool>> {
+    maze.data
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&value| value == MAZE_VALUE_ACCESSIBLE)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn get_open_neighbors(open: &[Vec<bool>], pos: (usize, usize)) -> Vec<(usize, usize)> {
+    [
+        AbsoluteDirection::Right,
+        AbsoluteDirection::Down,
+        AbsoluteDirection::Left,
+        AbsoluteDirection::Up,
+    ]
+    .iter()
+    .map(|direction| direction.apply(pos))
+    .filter(|&(x, y)| open[y][x])
+    .collect()
+}
+
+// Fills the dead ends until there are none left and returns the filled cells (in order).
+pub fn fill_dead_ends(
+    maze: &Maze,
+    open: &mut [Vec<bool>],
+    events: &mut dyn MazeEventHandler,
+) -> Vec<(usize, usize)> {
+    let mut filled_cells: Vec<(usize, usize)> = Vec::new();
+    // Check every open cell. The last one is checked first, so the maze is filled from the
+    // top left corner on.
+    let mut candidates: Vec<(usize, usize)> = (0..maze.height)
+        .rev()
+        .flat_map(|y| (0..maze.width).rev().map(move |x| (x, y)))
+        .filter(|&(x, y)| open[y][x])
+        .collect();
+    while let Some(pos) = candidates.pop() {
+        if !open[pos.1][pos.0] || pos == maze.pos_start || pos == maze.pos_end {
+            continue;
+        }
+        let neighbors = get_open_neighbors(open, pos);
+        if neighbors.len() > 1 {
+            continue;
+        }
+        open[pos.1][pos.0] = false;
+        filled_cells.push(pos);
+        events.handle(maze, MazeEvent::WallAdded { pos });
+        events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+        // Follow the dead end back, its neighbor is checked next.
+        candidates.extend(neighbors);
+    }
+    filled_cells
+}
+
+// Walks from the start to the end position through the open cells (breadth first, so the
+// path is the shortest one through the loops that are left). Returns no path if the end
+// position is not reachable.
+pub fn walk_through_open_cells(
+    maze: &mut Maze,
+    open: &[Vec<bool>],
+    events: &mut dyn MazeEventHandler,
+) -> Vec<(usize, usize)> {
+    let mut queue = VecDeque::from([(maze.pos_start, vec![maze.pos_start])]);
+    let mut visited_cells: HashSet<(usize, usize)> = HashSet::from([maze.pos_start]);
+    while let Some((pos, path)) = queue.pop_front() {
+        maze.inspection_order.push(pos);
+        events.handle(
+            maze,
+            MazeEvent::CellInspected {
+                pos,
+                direction: None,
+                highlight: CellColorType::InspectedCell,
+            },
+        );
+        if pos == maze.pos_end {
+            events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+            return path;
+        }
+        events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+        for pos_next in get_open_neighbors(open, pos) {
+            if visited_cells.insert(pos_next) {
+                let mut path_next = path.clone();
+                path_next.push(pos_next);
+                queue.push_back((pos_next, path_next));
+            }
+        }
+    }
+    Vec::new()
+}
@@ -0,0 +1,86 @@
+use crate::maze::direction::AbsoluteDirection;
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{BTreeSet, HashSet};
+
+pub struct Dijkstra;
+
+impl MazeSolver for Dijkstra {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Start with the start position and a cost of 0 in the queue.
+        2. Take the cell with the lowest cost from the queue. If it was already inspected,
+            it was reached cheaper before, skip it.
+        3. If it is the end position, the path is the cheapest one.
+        4. Otherwise add all accessible neighbors that are not inspected yet with the cost of
+            the path plus the cost of walking into the neighbor.
+        */
+        // The queue holds (cost, position, path), so the cheapest path comes first.
+        let mut queue = BTreeSet::new();
+        // Add the start position.
+        queue.insert((0, maze.pos_start, vec![maze.pos_start]));
+
+        // Count the inspected cells.
+        let mut inspected_cells: HashSet<(usize, usize)> = HashSet::new();
+
+        while let Some((cost, pos, path)) = queue.pop_first() {
+            if !inspected_cells.insert(pos) {
+                continue;
+            }
+            maze.inspection_order.push(pos);
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
+            if pos == maze.pos_end {
+                events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+                return (path, inspected_cells.len());
+            }
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+
+            for next_direction in [
+                AbsoluteDirection::Right,
+                AbsoluteDirection::Down,
+                AbsoluteDirection::Left,
+                AbsoluteDirection::Up,
+            ] {
+                let pos_next = next_direction.apply(pos);
+                if maze.is_accessible(pos_next) && !inspected_cells.contains(&pos_next) {
+                    let mut path_next = path.clone();
+                    path_next.push(pos_next);
+                    queue.insert((cost + maze.cost(pos_next), pos_next, path_next));
+                }
+            }
+            if events.animate() {
+                let frontier = queue.iter().map(|(cost, pos, _)| (*pos, *cost)).collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
+        }
+        // The end position is not reachable.
+        (Vec::new(), inspected_cells.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "g (cost)"
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Dijkstra")
+    }
+}
@@ -0,0 +1,86 @@
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::path::join_paths;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{HashSet, VecDeque};
+
+pub struct GraphBreadthFirstSearch;
+
+impl MazeSolver for GraphBreadthFirstSearch {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Take the graph of the maze (see MazeGraph): the nodes (corners, dead ends, junctions and the
+            start and end position) and the corridors between them.
+        2. Run the breadth-first search on the nodes instead of the cells. The cells in the
+            corridors are skipped, only the nodes are inspected.
+        The path has the fewest nodes. In a braided maze, this is not necessarily the shortest
+        path (a corridor counts the same no matter how long it is).
+        */
+        // The graph is built once with the maze, only the start and the end position are added.
+        let graph = maze.graph.splice(maze);
+        // The queue holds (number of edges, node, path).
+        let mut queue = VecDeque::from([(0, maze.pos_start, vec![maze.pos_start])]);
+
+        // Count the inspected nodes.
+        let mut inspected_nodes: HashSet<(usize, usize)> = HashSet::new();
+        // And the cells of their corridors (the cells a solver without the graph walks through).
+        let mut covered_cells: HashSet<(usize, usize)> = HashSet::new();
+
+        while let Some((number_of_edges, pos, path)) = queue.pop_front() {
+            if !inspected_nodes.insert(pos) {
+                continue;
+            }
+            maze.inspection_order.push(pos);
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
+            if pos == maze.pos_end {
+                events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+                maze.number_of_covered_cells = Some(covered_cells.len());
+                return (path, inspected_nodes.len());
+            }
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+
+            for edge in graph.edges(pos) {
+                covered_cells.extend(edge.cells.iter());
+                if !inspected_nodes.contains(&edge.node_to) {
+                    queue.push_back((
+                        number_of_edges + 1,
+                        edge.node_to,
+                        join_paths(&path, &edge.cells),
+                    ));
+                }
+            }
+            if events.animate() {
+                let frontier = queue
+                    .iter()
+                    .map(|(number_of_edges, pos, _)| (*pos, *number_of_edges))
+                    .collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
+        }
+        // The end position is not reachable.
+        maze.number_of_covered_cells = Some(covered_cells.len());
+        (Vec::new(), inspected_nodes.len())
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "edges"
+    }
+
+    fn to_string(&self) -> String {
+        String::from("graph breadth-first search")
+    }
+}
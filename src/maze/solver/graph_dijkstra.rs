@@ -0,0 +1,88 @@
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::path::join_paths;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{BTreeSet, HashSet};
+
+pub struct GraphDijkstra;
+
+impl MazeSolver for GraphDijkstra {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Take the graph of the maze (see MazeGraph): the nodes (corners, dead ends, junctions and the
+            start and end position) and the corridors between them. The cost of a corridor is
+            the cost of walking through its cells.
+        2. Run Dijkstra on the nodes instead of the cells (see Dijkstra). The cells in the
+            corridors are skipped, only the nodes are inspected.
+        */
+        // The graph is built once with the maze, only the start and the end position are added.
+        let graph = maze.graph.splice(maze);
+        // The queue holds (cost, node, path), so the cheapest path comes first.
+        let mut queue = BTreeSet::new();
+        // Add the start position.
+        queue.insert((0, maze.pos_start, vec![maze.pos_start]));
+
+        // Count the inspected nodes.
+        let mut inspected_nodes: HashSet<(usize, usize)> = HashSet::new();
+        // And the cells of their corridors (the cells a solver without the graph walks through).
+        let mut covered_cells: HashSet<(usize, usize)> = HashSet::new();
+
+        while let Some((cost, pos, path)) = queue.pop_first() {
+            if !inspected_nodes.insert(pos) {
+                continue;
+            }
+            maze.inspection_order.push(pos);
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos,
+                    direction: None,
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
+            if pos == maze.pos_end {
+                events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+                maze.number_of_covered_cells = Some(covered_cells.len());
+                return (path, inspected_nodes.len());
+            }
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+
+            for edge in graph.edges(pos) {
+                covered_cells.extend(edge.cells.iter());
+                if !inspected_nodes.contains(&edge.node_to) {
+                    queue.insert((
+                        cost + edge.cost,
+                        edge.node_to,
+                        join_paths(&path, &edge.cells),
+                    ));
+                }
+            }
+            if events.animate() {
+                let frontier = queue.iter().map(|(cost, pos, _)| (*pos, *cost)).collect();
+                events.handle(maze, MazeEvent::FrontierChanged { frontier });
+            }
+        }
+        // The end position is not reachable.
+        maze.number_of_covered_cells = Some(covered_cells.len());
+        (Vec::new(), inspected_nodes.len())
+    }
+
+    fn finds_shortest_path(&self) -> bool {
+        true
+    }
+
+    fn frontier_score_name(&self) -> &str {
+        "g (cost)"
+    }
+
+    fn to_string(&self) -> String {
+        String::from("graph Dijkstra")
+    }
+}
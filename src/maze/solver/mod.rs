@@ -1,53 +1,106 @@
 use crate::maze::animation::Delay;
+use crate::maze::event::MazeEventHandler;
 use crate::maze::maze::Maze;
-use std::io::Write;
 
 pub const SOLVING_DELAY: Delay = Delay::Long;
 
 pub mod a_star;
 pub mod a_star_weighted;
+pub mod bidirectional_a_star;
+pub mod bidirectional_breadth_first_search;
 pub mod breadth_first_search;
+pub mod cul_de_sac_filling;
+pub mod dead_end_filling;
 pub mod depth_first_search;
+pub mod dijkstra;
+pub mod graph_a_star;
+pub mod graph_breadth_first_search;
+pub mod graph_dijkstra;
 pub mod greedy_best_first_search;
+pub mod pledge;
+pub mod tremaux;
 pub mod wall_follower;
 
 pub use a_star::AStar;
 pub use a_star_weighted::AStarWeighted;
+pub use bidirectional_a_star::BidirectionalAStar;
+pub use bidirectional_breadth_first_search::BidirectionalBreadthFirstSearch;
 pub use breadth_first_search::BreadthFirstSearch;
+pub use cul_de_sac_filling::CulDeSacFilling;
+pub use dead_end_filling::DeadEndFilling;
 pub use depth_first_search::DepthFirstSearch;
+pub use dijkstra::Dijkstra;
+pub use graph_a_star::GraphAStar;
+pub use graph_breadth_first_search::GraphBreadthFirstSearch;
+pub use graph_dijkstra::GraphDijkstra;
 pub use greedy_best_first_search::GreedyBestFirstSearch;
-pub use wall_follower::WallFollower;
+pub use pledge::Pledge;
+pub use tremaux::Tremaux;
+pub use wall_follower::{WallFollower, WallSide};
 
 pub trait MazeSolver: Send + Sync {
     fn solve(
         &self,
         maze: &mut Maze,
-        screen: &mut dyn Write,
-        animate: bool,
+        events: &mut dyn MazeEventHandler,
     ) -> (Vec<(usize, usize)>, usize);
 
+    // Whether the solver always finds the shortest path, also in a braided maze (without
+    // terrain, see Dijkstra for the cheapest path through it).
+    fn finds_shortest_path(&self) -> bool {
+        false
+    }
+
+    // The name of the score the frontier is ordered by (shown in the step debugger).
+    fn frontier_score_name(&self) -> &str {
+        "score"
+    }
+
     fn to_string(&self) -> String;
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MazeSolvingAlgorithms {
     AStar,
     AStarWeighted,
+    BidirectionalAStar,
+    BidirectionalBreadthFirstSearch,
     BreadthFirstSearch,
+    CulDeSacFilling,
+    DeadEndFilling,
     DepthFirstSearch,
+    Dijkstra,
+    GraphAStar,
+    GraphBreadthFirstSearch,
+    GraphDijkstra,
     GreedyBestFirstSearch,
-    WallFollower,
+    Pledge,
+    Tremaux,
+    WallFollower(WallSide),
 }
 
 impl MazeSolvingAlgorithms {
     pub fn next(&self) -> Self {
         match self {
             Self::AStar => Self::AStarWeighted,
-            Self::AStarWeighted => Self::BreadthFirstSearch,
-            Self::BreadthFirstSearch => Self::DepthFirstSearch,
-            Self::DepthFirstSearch => Self::GreedyBestFirstSearch,
-            Self::GreedyBestFirstSearch => Self::WallFollower,
-            Self::WallFollower => Self::AStar,
+            Self::AStarWeighted => Self::BidirectionalAStar,
+            Self::BidirectionalAStar => Self::BidirectionalBreadthFirstSearch,
+            Self::BidirectionalBreadthFirstSearch => Self::BreadthFirstSearch,
+            Self::BreadthFirstSearch => Self::CulDeSacFilling,
+            Self::CulDeSacFilling => Self::DeadEndFilling,
+            Self::DeadEndFilling => Self::DepthFirstSearch,
+            Self::DepthFirstSearch => Self::Dijkstra,
+            Self::Dijkstra => Self::GraphAStar,
+            Self::GraphAStar => Self::GraphBreadthFirstSearch,
+            Self::GraphBreadthFirstSearch => Self::GraphDijkstra,
+            Self::GraphDijkstra => Self::GreedyBestFirstSearch,
+            Self::GreedyBestFirstSearch => Self::Pledge,
+            Self::Pledge => Self::Tremaux,
+            Self::Tremaux => Self::WallFollower(WallSide::Right),
+            Self::WallFollower(side) => match side.next() {
+                Some(side) => Self::WallFollower(side),
+                None => Self::AStar,
+            },
         }
     }
 
@@ -55,10 +108,181 @@ impl MazeSolvingAlgorithms {
         match self {
             Self::AStar => "A*",
             Self::AStarWeighted => "A* weighted",
+            Self::BidirectionalAStar => "bidirectional A*",
+            Self::BidirectionalBreadthFirstSearch => "bidirectional BFS",
             Self::BreadthFirstSearch => "BFS",
+            Self::CulDeSacFilling => "cul-de-sac filling",
+            Self::DeadEndFilling => "dead-end filling",
             Self::DepthFirstSearch => "DFS",
+            Self::Dijkstra => "Dijkstra",
+            Self::GraphAStar => "graph A*",
+            Self::GraphBreadthFirstSearch => "graph BFS",
+            Self::GraphDijkstra => "graph Dijkstra",
             Self::GreedyBestFirstSearch => "greedy best-first search",
-            Self::WallFollower => "wall follower",
+            Self::Pledge => "Pledge",
+            Self::Tremaux => "Trémaux",
+            Self::WallFollower(side) => match side {
+                WallSide::Right => "wall follower (right)",
+                WallSide::Left => "wall follower (left)",
+            },
+        }
+    }
+
+    // All algorithms in the order they are cycled through.
+    pub fn all() -> Vec<Self> {
+        let mut algorithms = vec![Self::AStar];
+        loop {
+            let algorithm = algorithms.last().unwrap().next();
+            if algorithm == Self::AStar {
+                return algorithms;
+            }
+            algorithms.push(algorithm);
+        }
+    }
+
+    // The name used on the command line.
+    pub fn to_command_line_name(&self) -> &str {
+        match self {
+            Self::AStar => "astar",
+            Self::AStarWeighted => "astar-weighted",
+            Self::BidirectionalAStar => "bidirectional-astar",
+            Self::BidirectionalBreadthFirstSearch => "bidirectional-bfs",
+            Self::BreadthFirstSearch => "bfs",
+            Self::CulDeSacFilling => "cul-de-sac-filling",
+            Self::DeadEndFilling => "dead-end-filling",
+            Self::DepthFirstSearch => "dfs",
+            Self::Dijkstra => "dijkstra",
+            Self::GraphAStar => "graph-astar",
+            Self::GraphBreadthFirstSearch => "graph-bfs",
+            Self::GraphDijkstra => "graph-dijkstra",
+            Self::GreedyBestFirstSearch => "greedy",
+            Self::Pledge => "pledge",
+            Self::Tremaux => "tremaux",
+            Self::WallFollower(side) => match side {
+                WallSide::Right => "wall-follower-right",
+                WallSide::Left => "wall-follower-left",
+            },
+        }
+    }
+
+    pub fn from_command_line_name(name: &str) -> Option<Self> {
+        // The name from before the wall follower got its left-hand variant.
+        if name == "wall-follower" {
+            return Some(Self::WallFollower(WallSide::Right));
+        }
+        Self::all()
+            .into_iter()
+            .find(|algorithm| algorithm.to_command_line_name() == name)
+    }
+
+    pub fn to_solver(&self) -> Box<dyn MazeSolver> {
+        match self {
+            Self::AStar => Box::new(AStar),
+            Self::AStarWeighted => Box::new(AStarWeighted),
+            Self::BidirectionalAStar => Box::new(BidirectionalAStar),
+            Self::BidirectionalBreadthFirstSearch => Box::new(BidirectionalBreadthFirstSearch),
+            Self::BreadthFirstSearch => Box::new(BreadthFirstSearch),
+            Self::CulDeSacFilling => Box::new(CulDeSacFilling),
+            Self::DeadEndFilling => Box::new(DeadEndFilling),
+            Self::DepthFirstSearch => Box::new(DepthFirstSearch),
+            Self::Dijkstra => Box::new(Dijkstra),
+            Self::GraphAStar => Box::new(GraphAStar),
+            Self::GraphBreadthFirstSearch => Box::new(GraphBreadthFirstSearch),
+            Self::GraphDijkstra => Box::new(GraphDijkstra),
+            Self::GreedyBestFirstSearch => Box::new(GreedyBestFirstSearch),
+            Self::Pledge => Box::new(Pledge),
+            Self::Tremaux => Box::new(Tremaux),
+            Self::WallFollower(side) => Box::new(WallFollower { side: *side }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::direction::AbsoluteDirection;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::{Braid, MazeGenerationAlgorithms};
+    use crate::maze::maze::MAZE_VALUE_BLOCKED;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Generates a maze (braided if dead_end_removal > 0) for every generation algorithm.
+    fn generate_mazes(dead_end_removal: f64, seed: u64) -> Vec<Maze> {
+        MazeGenerationAlgorithms::all()
+            .iter()
+            .map(|algorithm| {
+                let mut maze = Maze::new(31, 21, (1, 1));
+                maze.change_size(31, 21);
+                let generator = Braid::wrap(algorithm.to_generator(), dead_end_removal);
+                maze.generate(&*generator, seed, &mut NullEventHandler);
+                maze
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shortest_path_solvers_find_the_path_of_the_breadth_first_search() {
+        for seed in 0..3 {
+            for mut maze in generate_mazes(0.5, seed) {
+                let mut rng = StdRng::seed_from_u64(seed);
+                for _ in 0..5 {
+                    maze.set_random_start_end_position(&mut rng);
+                    let (path_bfs, _) = maze.solve(&BreadthFirstSearch, &mut NullEventHandler);
+                    for algorithm in MazeSolvingAlgorithms::all() {
+                        let solver = algorithm.to_solver();
+                        if !solver.finds_shortest_path() {
+                            continue;
+                        }
+                        let (path, _) = maze.solve(&*solver, &mut NullEventHandler);
+                        assert_eq!(
+                            path.len(),
+                            path_bfs.len(),
+                            "{} (seed {})",
+                            solver.to_string(),
+                            seed
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_solver_finds_the_path_in_a_perfect_maze() {
+        for mut maze in generate_mazes(0.0, 7) {
+            let (path_bfs, _) = maze.solve(&BreadthFirstSearch, &mut NullEventHandler);
+            for algorithm in MazeSolvingAlgorithms::all() {
+                let solver = algorithm.to_solver();
+                let (path, _) = maze.solve(&*solver, &mut NullEventHandler);
+                // There is only one path, also the solvers that walk around find it.
+                assert_eq!(path, path_bfs, "{}", solver.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn every_solver_finds_no_path_if_there_is_none() {
+        for maze in generate_mazes(0.5, 7) {
+            // Wall in the end position, then (in a fresh copy of the maze) the start position.
+            for pos_walled_in in [maze.pos_end, maze.pos_start] {
+                let mut data = maze.data.clone();
+                for direction in [
+                    AbsoluteDirection::Right,
+                    AbsoluteDirection::Down,
+                    AbsoluteDirection::Left,
+                    AbsoluteDirection::Up,
+                ] {
+                    let pos = direction.apply(pos_walled_in);
+                    data[pos.1][pos.0] = MAZE_VALUE_BLOCKED;
+                }
+                let mut maze_walled_in = Maze::from_data(data, maze.pos_start, maze.pos_end);
+                for algorithm in MazeSolvingAlgorithms::all() {
+                    let solver = algorithm.to_solver();
+                    let (path, _) = maze_walled_in.solve(&*solver, &mut NullEventHandler);
+                    assert!(path.is_empty(), "{}", solver.to_string());
+                }
+            }
         }
     }
 }
@@ -0,0 +1,149 @@
+use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::path::erase_loops;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::{HashMap, HashSet};
+
+pub struct Pledge;
+
+impl MazeSolver for Pledge {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Choose a main direction (towards the end position) and walk straight in it.
+        2. At a wall, turn left and follow the wall on the right side (like the wall follower).
+            Count the turns: +1 for every right turn, -1 for every left turn.
+        3. As soon as the sum of the turns is 0 again, you face the main direction. Leave the
+            wall and continue with 1.
+        Unlike the wall follower, this gets away from walls that are not connected to the
+        outer wall. But the end position is only reached if it lies on a wall that is
+        followed. If the solver ends up walking in circles (or cannot walk at all), it gives up
+        (and returns no path).
+        */
+        let main_direction = if maze.pos_end.0.abs_diff(maze.pos_start.0)
+            >= maze.pos_end.1.abs_diff(maze.pos_start.1)
+        {
+            if maze.pos_end.0 >= maze.pos_start.0 {
+                AbsoluteDirection::Right
+            } else {
+                AbsoluteDirection::Left
+            }
+        } else if maze.pos_end.1 >= maze.pos_start.1 {
+            AbsoluteDirection::Down
+        } else {
+            AbsoluteDirection::Up
+        };
+        let is_walled_in = [
+            AbsoluteDirection::Right,
+            AbsoluteDirection::Down,
+            AbsoluteDirection::Left,
+            AbsoluteDirection::Up,
+        ]
+        .iter()
+        .all(|direction| !maze.is_accessible(direction.apply(maze.pos_start)));
+        if is_walled_in && maze.pos_start != maze.pos_end {
+            // The solver cannot walk at all.
+            return (Vec::new(), 0);
+        }
+        let mut pos_current = maze.pos_start;
+        let mut direction = main_direction;
+        let mut turns: isize = 0;
+        let mut follows_wall = false;
+        let mut path_walked: Vec<(usize, usize)> = vec![pos_current];
+
+        // To detect walking in circles: where the solver hit a wall while walking straight (it
+        // would do the same again from there) and when it was where while following a wall.
+        let mut wall_hits: HashSet<(usize, usize)> = HashSet::new();
+        let mut wall_visits: HashMap<((usize, usize), AbsoluteDirection), (usize, isize)> =
+            HashMap::new();
+
+        // Count the inspected cells.
+        let mut inspected_cells: HashSet<(usize, usize)> = HashSet::new();
+
+        events.handle(
+            maze,
+            MazeEvent::CellInspected {
+                pos: pos_current,
+                direction: None,
+                highlight: CellColorType::InspectedCell,
+            },
+        );
+        while pos_current != maze.pos_end {
+            if inspected_cells.insert(pos_current) {
+                maze.inspection_order.push(pos_current);
+            }
+            if !follows_wall {
+                if maze.is_accessible(main_direction.apply(pos_current)) {
+                    direction = main_direction;
+                } else {
+                    if !wall_hits.insert(pos_current) {
+                        return (Vec::new(), inspected_cells.len());
+                    }
+                    wall_visits.clear();
+                    follows_wall = true;
+                    // Turn left until the wall is on the right side.
+                    while !maze.is_accessible(direction.apply(pos_current)) {
+                        direction = direction.add_relative_direction(RelativeDirection::Left);
+                        turns -= 1;
+                    }
+                }
+            } else {
+                // Follow the right wall.
+                direction = direction.add_relative_direction(RelativeDirection::Right);
+                turns += 1;
+                while !maze.is_accessible(direction.apply(pos_current)) {
+                    direction = direction.add_relative_direction(RelativeDirection::Left);
+                    turns -= 1;
+                }
+            }
+            pos_current = direction.apply(pos_current);
+            path_walked.push(pos_current);
+            if follows_wall {
+                if turns == 0 {
+                    follows_wall = false;
+                } else if let Some((steps_before, turns_before)) =
+                    wall_visits.insert((pos_current, direction), (path_walked.len(), turns))
+                {
+                    // A full round along the wall. Every step turns by two at most, so if the
+                    // turns moved further away from 0, they will never be 0 again.
+                    let steps_round = (path_walked.len() - steps_before) as isize;
+                    if turns == turns_before
+                        || (turns.signum() == (turns - turns_before).signum()
+                            && turns.abs() > 2 * steps_round)
+                    {
+                        return (Vec::new(), inspected_cells.len());
+                    }
+                }
+            }
+            // Mark the current cell including the walking direction.
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos: pos_current,
+                    direction: Some(direction),
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+        }
+        // Catch the last one.
+        if inspected_cells.insert(pos_current) {
+            maze.inspection_order.push(pos_current);
+        }
+
+        let path = erase_loops(&path_walked);
+        events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+        (path, inspected_cells.len())
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Pledge")
+    }
+}
@@ -0,0 +1,120 @@
+use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
+use std::collections::HashSet;
+
+// The marks Trémaux's algorithm leaves in the cells it walked through.
+const SYMBOL_MARKED_ONCE: char = '·';
+const SYMBOL_MARKED_TWICE: char = '×';
+
+pub struct Tremaux;
+
+impl MazeSolver for Tremaux {
+    fn solve(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Walk through the maze and mark every cell you walk through.
+        2. Only walk into cells without a mark (look right, forward and left first). A cell
+            with a mark is treated like a wall, this way the loops are never walked twice.
+        3. If there is no cell without a mark around, walk back the way you came and mark the
+            cell a second time. Cells with two marks are never visited again.
+        4. When the end position is reached, the cells with one mark are the path.
+        */
+        // How often the cells were walked through (0, 1 or 2).
+        let mut marks: Vec<Vec<u8>> = vec![vec![0; maze.width]; maze.height];
+        let mut pos_current = maze.pos_start;
+        let mut direction = AbsoluteDirection::Right;
+        // The cells with one mark, from the start position to the current cell.
+        let mut path: Vec<(usize, usize)> = vec![pos_current];
+        marks[pos_current.1][pos_current.0] = 1;
+
+        // Count the inspected cells.
+        let mut inspected_cells: HashSet<(usize, usize)> = HashSet::new();
+
+        events.handle(
+            maze,
+            MazeEvent::CellInspected {
+                pos: pos_current,
+                direction: None,
+                highlight: CellColorType::InspectedCell,
+            },
+        );
+        while pos_current != maze.pos_end {
+            if inspected_cells.insert(pos_current) {
+                maze.inspection_order.push(pos_current);
+            }
+            let pos_prev = pos_current;
+            let direction_unmarked = [
+                RelativeDirection::Right,
+                RelativeDirection::Forward,
+                RelativeDirection::Left,
+                RelativeDirection::Backward,
+            ]
+            .iter()
+            .map(|&relative_direction| direction.add_relative_direction(relative_direction))
+            .find(|next_direction| {
+                let pos_next = next_direction.apply(pos_current);
+                maze.is_accessible(pos_next) && marks[pos_next.1][pos_next.0] == 0
+            });
+            match direction_unmarked {
+                Some(next_direction) => {
+                    direction = next_direction;
+                    pos_current = direction.apply(pos_current);
+                    marks[pos_current.1][pos_current.0] = 1;
+                    path.push(pos_current);
+                }
+                None => {
+                    // Walk back.
+                    marks[pos_current.1][pos_current.0] = 2;
+                    path.pop();
+                    // Back at the start position and every cell is marked twice: the end
+                    // position is not reachable.
+                    let Some(&pos_back) = path.last() else {
+                        return (Vec::new(), inspected_cells.len());
+                    };
+                    direction = AbsoluteDirection::from_points(pos_current, pos_back);
+                    pos_current = pos_back;
+                }
+            }
+            events.handle(
+                maze,
+                MazeEvent::CellInspected {
+                    pos: pos_current,
+                    direction: Some(direction),
+                    highlight: CellColorType::InspectedCell,
+                },
+            );
+            if pos_prev != maze.pos_start {
+                events.handle(
+                    maze,
+                    MazeEvent::CellMarked {
+                        pos: pos_prev,
+                        symbol: match marks[pos_prev.1][pos_prev.0] {
+                            1 => SYMBOL_MARKED_ONCE,
+                            _ => SYMBOL_MARKED_TWICE,
+                        },
+                    },
+                );
+            }
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
+        }
+        // Catch the last one.
+        if inspected_cells.insert(pos_current) {
+            maze.inspection_order.push(pos_current);
+        }
+
+        events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+        (path, inspected_cells.len())
+    }
+
+    fn to_string(&self) -> String {
+        String::from("Trémaux")
+    }
+}
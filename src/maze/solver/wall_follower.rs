@@ -1,21 +1,75 @@
-use crate::maze::animation::delay;
 use crate::maze::direction::{AbsoluteDirection, RelativeDirection};
-use crate::maze::draw::{draw_character, highlight_cell, CellColorType};
+use crate::maze::draw::CellColorType;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
 use crate::maze::maze::Maze;
+use crate::maze::path::erase_loops;
 use crate::maze::solver::{MazeSolver, SOLVING_DELAY};
 use std::collections::HashSet;
 
-pub struct WallFollower;
+// The side of the wall the hand is kept on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallSide {
+    Right,
+    Left,
+}
+
+impl WallSide {
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::Right => Some(Self::Left),
+            Self::Left => None,
+        }
+    }
+}
+
+pub struct WallFollower {
+    pub side: WallSide,
+}
 
 impl MazeSolver for WallFollower {
     fn solve(
         &self,
         maze: &mut Maze,
-        screen: &mut dyn std::io::Write,
-        animate: bool,
+        events: &mut dyn MazeEventHandler,
     ) -> (Vec<(usize, usize)>, usize) {
+        /*
+        Algorithm:
+
+        1. Keep one hand on the wall (right or left) and walk along it until the end position
+            is reached.
+        2. The walked route contains every dead end on the way twice (in and out again).
+            Erase these loops, what is left is the path (the shortest one in a perfect maze).
+            Both the walked route and the path are reported.
+        If the start position lies on an island (a wall that is not connected to the outer
+        wall, only in braided mazes), the solver walks around it forever. As soon as it is at
+        the same cell facing the same direction again, it gives up (and returns no path).
+        */
+        let Some(route) = self.walk_along_wall(maze, events) else {
+            return (Vec::new(), maze.inspection_order.len());
+        };
+        let path = erase_loops(&route);
+        events.handle(maze, MazeEvent::RouteWalked { route });
+        events.handle(maze, MazeEvent::PathFound { path: path.clone() });
+        (path, maze.inspection_order.len())
+    }
+
+    fn to_string(&self) -> String {
+        match self.side {
+            WallSide::Right => String::from("wall follower (right)"),
+            WallSide::Left => String::from("wall follower (left)"),
+        }
+    }
+}
+
+impl WallFollower {
+    // Returns the walked route from the start to the end position, None if the solver walks
+    // in circles (or cannot walk at all).
+    fn walk_along_wall(
+        &self,
+        maze: &mut Maze,
+        events: &mut dyn MazeEventHandler,
+    ) -> Option<Vec<(usize, usize)>> {
         let mut pos_current = maze.pos_start;
-        let mut pos_prev;
         let mut direction = match (
             maze.is_accessible((pos_current.0, pos_current.1 - 1)),
             maze.is_accessible((pos_current.0 - 1, pos_current.1)),
@@ -26,45 +80,60 @@ impl MazeSolver for WallFollower {
             (_, true, _, _) => AbsoluteDirection::Left,
             (_, _, true, _) => AbsoluteDirection::Down,
             (_, _, _, true) => AbsoluteDirection::Right,
-            _ => panic!(),
+            // The start position is walled in.
+            _ => return None,
         };
+        let (towards_wall, away_from_wall) = match self.side {
+            WallSide::Right => (RelativeDirection::Right, RelativeDirection::Left),
+            WallSide::Left => (RelativeDirection::Left, RelativeDirection::Right),
+        };
+        let mut path_walked: Vec<(usize, usize)> = vec![pos_current];
+        // Where the solver was, facing which direction.
+        let mut visited_states: HashSet<((usize, usize), AbsoluteDirection)> = HashSet::new();
 
         // Count the inspected cells.
         let mut inspected_cells: HashSet<(usize, usize)> = HashSet::new();
 
+        events.handle(
+            maze,
+            MazeEvent::CellInspected {
+                pos: pos_current,
+                direction: None,
+                highlight: CellColorType::InspectedCell,
+            },
+        );
         while pos_current != maze.pos_end {
-            inspected_cells.insert(pos_current);
-            pos_prev = pos_current;
-            // Follow the right wall.
-            direction = direction.add_relative_direction(RelativeDirection::Right);
+            if inspected_cells.insert(pos_current) {
+                maze.inspection_order.push(pos_current);
+            }
+            // Follow the wall: turn towards it first, then away from it until the way is free.
+            direction = direction.add_relative_direction(towards_wall);
             loop {
                 if maze.is_accessible(direction.apply(pos_current)) {
                     break;
                 }
-                direction = direction.add_relative_direction(RelativeDirection::Left);
+                direction = direction.add_relative_direction(away_from_wall);
             }
             pos_current = direction.apply(pos_current);
-            // Highlight the previous cell.
-            highlight_cell(screen, maze, pos_prev, CellColorType::InspectedCell);
+            path_walked.push(pos_current);
+            if !visited_states.insert((pos_current, direction)) {
+                return None;
+            }
             // Mark the current cell including the walking direction.
-            draw_character(
-                screen,
+            events.handle(
                 maze,
-                pos_current,
-                direction.to_symbol(),
-                Some(CellColorType::CurrentCell),
+                MazeEvent::CellInspected {
+                    pos: pos_current,
+                    direction: Some(direction),
+                    highlight: CellColorType::InspectedCell,
+                },
             );
-            if animate {
-                delay(SOLVING_DELAY);
-            }
+            events.handle(maze, MazeEvent::Pause(SOLVING_DELAY));
         }
         // Catch the last one.
-        inspected_cells.insert(pos_current);
-
-        (Vec::new(), inspected_cells.len())
-    }
-
-    fn to_string(&self) -> String {
-        String::from("wall follower")
+        if inspected_cells.insert(pos_current) {
+            maze.inspection_order.push(pos_current);
+        }
+        Some(path_walked)
     }
 }
@@ -0,0 +1,342 @@
+use crate::maze::draw::TerminalRenderer;
+use crate::maze::event::{MazeEvent, MazeEventHandler};
+use crate::maze::maze::Maze;
+use crate::maze::solver::MazeSolver;
+use std::collections::HashMap;
+use std::io::Write;
+
+type FrontierEntry = ((usize, usize), usize);
+
+// The change from one frontier to the next one: the entries that were removed (with their index
+// in the old frontier) and the ones that were added (with their index in the new frontier). The
+// solvers report the whole frontier every time, but only a few entries change between two steps.
+#[derive(Default)]
+struct FrontierDiff {
+    removed: Vec<(usize, FrontierEntry)>,
+    added: Vec<(usize, FrontierEntry)>,
+}
+
+impl FrontierDiff {
+    fn new(frontier_old: &[FrontierEntry], frontier_new: &[FrontierEntry]) -> Self {
+        // Walk through both frontiers. Equal entries are kept, an old entry that does not
+        // come again in the new frontier is removed, everything else is added.
+        let mut remaining_new: HashMap<FrontierEntry, usize> = HashMap::new();
+        for &entry in frontier_new {
+            *remaining_new.entry(entry).or_default() += 1;
+        }
+        let mut diff = FrontierDiff::default();
+        let (mut idx_old, mut idx_new) = (0, 0);
+        while idx_old < frontier_old.len() || idx_new < frontier_new.len() {
+            let entry_old = frontier_old.get(idx_old);
+            let entry_new = frontier_new.get(idx_new);
+            if entry_old.is_some() && entry_old == entry_new {
+                *remaining_new.get_mut(entry_new.unwrap()).unwrap() -= 1;
+                idx_old += 1;
+                idx_new += 1;
+            } else if let Some(&entry_old) =
+                entry_old.filter(|entry| remaining_new.get(entry).copied().unwrap_or(0) == 0)
+            {
+                diff.removed.push((idx_old, entry_old));
+                idx_old += 1;
+            } else if let Some(&entry_new) = entry_new {
+                *remaining_new.get_mut(&entry_new).unwrap() -= 1;
+                diff.added.push((idx_new, entry_new));
+                idx_new += 1;
+            } else {
+                diff.removed.push((idx_old, frontier_old[idx_old]));
+                idx_old += 1;
+            }
+        }
+        diff
+    }
+
+    // Removes the given entries (back to front, so the indices stay valid) and inserts the
+    // others (front to back).
+    fn change(
+        frontier: &mut Vec<FrontierEntry>,
+        removed: &[(usize, FrontierEntry)],
+        added: &[(usize, FrontierEntry)],
+    ) {
+        for &(idx, _) in removed.iter().rev() {
+            frontier.remove(idx);
+        }
+        for &(idx, entry) in added {
+            frontier.insert(idx, entry);
+        }
+    }
+
+    fn apply(&self, frontier: &mut Vec<FrontierEntry>) {
+        FrontierDiff::change(frontier, &self.removed, &self.added);
+    }
+
+    fn revert(&self, frontier: &mut Vec<FrontierEntry>) {
+        FrontierDiff::change(frontier, &self.added, &self.removed);
+    }
+}
+
+// The events of one step and how the frontier changed during it.
+#[derive(Default)]
+struct Step {
+    events: Vec<MazeEvent>,
+    frontier_diffs: Vec<FrontierDiff>,
+}
+
+// Collects the events of a solver. Every inspected cell and every added wall (of the filling
+// solvers) starts a new step.
+struct StepRecorder {
+    steps: Vec<Step>,
+    // The frontier after the last step, the steps only keep the changes.
+    frontier: Vec<FrontierEntry>,
+}
+
+impl MazeEventHandler for StepRecorder {
+    fn handle(&mut self, _maze: &Maze, event: MazeEvent) {
+        match event {
+            // The debugger draws the maze itself and waits for key presses instead of pausing.
+            MazeEvent::MazeChanged | MazeEvent::Pause(_) => return,
+            MazeEvent::CellInspected { .. } | MazeEvent::WallAdded { .. } => {
+                self.steps.push(Step::default())
+            }
+            _ if self.steps.is_empty() => self.steps.push(Step::default()),
+            _ => {}
+        }
+        let step = self.steps.last_mut().unwrap();
+        match event {
+            MazeEvent::FrontierChanged { frontier } => {
+                step.frontier_diffs
+                    .push(FrontierDiff::new(&self.frontier, &frontier));
+                self.frontier = frontier;
+            }
+            _ => step.events.push(event),
+        }
+    }
+
+    fn animate(&self) -> bool {
+        // Otherwise the solvers do not report their frontier.
+        true
+    }
+}
+
+// Runs a solver once and replays its steps one by one, forwards and backwards.
+pub struct StepDebugger {
+    steps: Vec<Step>,
+    // The number of steps that are shown.
+    current_step: usize,
+    // The frontier after the shown steps.
+    frontier: Vec<FrontierEntry>,
+    // The first step that reports a frontier (none for solvers without one, e.g. the wall
+    // follower).
+    first_frontier_step: Option<usize>,
+    pub path: Vec<(usize, usize)>,
+    pub number_of_inspected_cells: usize,
+}
+
+impl StepDebugger {
+    pub fn new(maze: &mut Maze, solver: &dyn MazeSolver) -> Self {
+        let mut recorder = StepRecorder {
+            steps: Vec::new(),
+            frontier: Vec::new(),
+        };
+        let (path, number_of_inspected_cells) = maze.solve(solver, &mut recorder);
+        let first_frontier_step = recorder
+            .steps
+            .iter()
+            .position(|step| !step.frontier_diffs.is_empty());
+        StepDebugger {
+            steps: recorder.steps,
+            current_step: 0,
+            frontier: Vec::new(),
+            first_frontier_step,
+            path,
+            number_of_inspected_cells,
+        }
+    }
+
+    pub fn step_forward(&mut self, screen: &mut dyn Write, maze: &Maze) {
+        if self.current_step == self.steps.len() {
+            return;
+        }
+        let mut renderer = TerminalRenderer::new(screen, false);
+        // Tell the renderer where the solver is, so this cell is marked as inspected now.
+        if let Some(event) = self.last_inspection() {
+            renderer.handle(maze, event.clone());
+        }
+        let step = &self.steps[self.current_step];
+        for event in step.events.iter() {
+            renderer.handle(maze, event.clone());
+        }
+        for frontier_diff in step.frontier_diffs.iter() {
+            frontier_diff.apply(&mut self.frontier);
+        }
+        self.current_step += 1;
+    }
+
+    pub fn step_backward(&mut self, screen: &mut dyn Write, maze: &Maze) {
+        if self.current_step == 0 {
+            return;
+        }
+        self.current_step -= 1;
+        for frontier_diff in self.steps[self.current_step].frontier_diffs.iter().rev() {
+            frontier_diff.revert(&mut self.frontier);
+        }
+        self.redraw(screen, maze);
+    }
+
+    // Draws the maze and replays all steps that are shown.
+    pub fn redraw(&self, screen: &mut dyn Write, maze: &Maze) {
+        let mut renderer = TerminalRenderer::new(screen, false);
+        renderer.handle(maze, MazeEvent::MazeChanged);
+        for event in self.steps[..self.current_step]
+            .iter()
+            .flat_map(|step| step.events.iter())
+        {
+            renderer.handle(maze, event.clone());
+        }
+    }
+
+    fn last_inspection(&self) -> Option<&MazeEvent> {
+        self.steps[..self.current_step]
+            .iter()
+            .rev()
+            .flat_map(|step| step.events.iter())
+            .find(|event| matches!(event, MazeEvent::CellInspected { .. }))
+    }
+
+    // The cells the solver will inspect next (after the shown steps).
+    fn frontier(&self) -> Option<&Vec<FrontierEntry>> {
+        self.first_frontier_step
+            .filter(|&step| step < self.current_step)
+            .map(|_| &self.frontier)
+    }
+
+    // The state of the solver as lines of text for the side panel.
+    pub fn describe(&self, score_name: &str) -> Vec<String> {
+        let mut lines = vec![format!("Step {} / {}", self.current_step, self.steps.len())];
+        if let Some(MazeEvent::CellInspected { pos, .. }) = self.last_inspection() {
+            lines.push(format!("Current: ({}, {})", pos.0, pos.1));
+        }
+        if self.current_step == self.steps.len() && !self.path.is_empty() {
+            lines.push(format!("Path found ({})", self.path.len()));
+        }
+        match self.frontier() {
+            Some(frontier) => {
+                lines.push(format!("Frontier ({}):", frontier.len()));
+                lines.push(format!("{:<10} {:>5}", "pos", score_name));
+                lines.extend(
+                    frontier
+                        .iter()
+                        .map(|(pos, score)| format!("({:>3}, {:>3}) {:>5}", pos.0, pos.1, score)),
+                );
+            }
+            None => lines.push(String::from("Frontier: -")),
+        }
+        lines
+    }
+
+    // The state of the solver in one line (if there is no space for the side panel).
+    pub fn summarize(&self, score_name: &str) -> String {
+        let mut summary = format!("Step {} / {}", self.current_step, self.steps.len());
+        if let Some(MazeEvent::CellInspected { pos, .. }) = self.last_inspection() {
+            summary.push_str(&format!(", current: ({}, {})", pos.0, pos.1));
+        }
+        if let Some(frontier) = self.frontier() {
+            summary.push_str(&format!(", frontier ({}): ", score_name));
+            summary.push_str(
+                &frontier
+                    .iter()
+                    .map(|(pos, score)| format!("({}, {}) {}", pos.0, pos.1, score))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            );
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::{Braid, MazeGenerationAlgorithms};
+    use crate::maze::solver::{AStar, BreadthFirstSearch, DeadEndFilling, DepthFirstSearch};
+
+    // Keeps the whole frontier after every step (what the debugger used to do).
+    struct FrontierRecorder {
+        frontiers: Vec<Option<Vec<FrontierEntry>>>,
+    }
+
+    impl MazeEventHandler for FrontierRecorder {
+        fn handle(&mut self, _maze: &Maze, event: MazeEvent) {
+            match event {
+                MazeEvent::CellInspected { .. } | MazeEvent::WallAdded { .. } => {
+                    let frontier = self.frontiers.last().cloned().flatten();
+                    self.frontiers.push(frontier);
+                }
+                MazeEvent::FrontierChanged { frontier } => match self.frontiers.last_mut() {
+                    Some(last) => *last = Some(frontier),
+                    None => self.frontiers.push(Some(frontier)),
+                },
+                _ => {}
+            }
+        }
+
+        fn animate(&self) -> bool {
+            true
+        }
+    }
+
+    fn generate_maze() -> Maze {
+        let mut maze = Maze::new(31, 21, (1, 1));
+        maze.change_size(31, 21);
+        let generator = Braid::wrap(MazeGenerationAlgorithms::all()[0].to_generator(), 0.5);
+        maze.generate(&*generator, 3, &mut NullEventHandler);
+        maze
+    }
+
+    #[test]
+    fn frontier_is_rebuilt_forwards_and_backwards() {
+        let solvers: [&dyn MazeSolver; 3] = [&AStar, &BreadthFirstSearch, &DepthFirstSearch];
+        for solver in solvers {
+            let mut maze = generate_maze();
+            let mut recorder = FrontierRecorder {
+                frontiers: Vec::new(),
+            };
+            maze.solve(solver, &mut recorder);
+            let mut step_debugger = StepDebugger::new(&mut maze, solver);
+            assert_eq!(step_debugger.steps.len(), recorder.frontiers.len());
+
+            let mut screen: Vec<u8> = Vec::new();
+            for frontier in recorder.frontiers.iter() {
+                step_debugger.step_forward(&mut screen, &maze);
+                assert_eq!(step_debugger.frontier(), frontier.as_ref());
+            }
+            for frontier in recorder.frontiers.iter().rev().skip(1) {
+                step_debugger.step_backward(&mut screen, &maze);
+                assert_eq!(step_debugger.frontier(), frontier.as_ref());
+            }
+        }
+    }
+
+    #[test]
+    fn every_filled_cell_is_a_step() {
+        let mut maze = generate_maze();
+        let step_debugger = StepDebugger::new(&mut maze, &DeadEndFilling);
+        let number_of_filled_cells = step_debugger
+            .steps
+            .iter()
+            .filter(|step| {
+                step.events
+                    .iter()
+                    .any(|event| matches!(event, MazeEvent::WallAdded { .. }))
+            })
+            .count();
+        assert!(number_of_filled_cells > 1);
+        assert!(step_debugger.steps.iter().all(|step| {
+            step.events
+                .iter()
+                .filter(|event| matches!(event, MazeEvent::WallAdded { .. }))
+                .count()
+                <= 1
+        }));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::maze::draw::CellColorType;
+use crate::maze::maze::*;
+
+// The size of one field of the maze in the SVG (in user units).
+const SVG_FIELD_SIZE: usize = 10;
+const SVG_COLOR_WALL: &str = "#000000";
+const SVG_COLOR_BACKGROUND: &str = "#ffffff";
+
+fn to_svg_color(rgb: (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
+}
+
+// Interpolates between two colors (t = 0 is the first, t = 1 the second color).
+fn interpolate_color(from: (u8, u8, u8), to: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let channel = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+    (
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+fn rect(pos: (usize, usize), width: usize, color: &str) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+        pos.0 * SVG_FIELD_SIZE,
+        pos.1 * SVG_FIELD_SIZE,
+        width * SVG_FIELD_SIZE,
+        SVG_FIELD_SIZE,
+        color
+    )
+}
+
+fn circle(pos: (usize, usize), color: &str) -> String {
+    format!(
+        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+        pos.0 * SVG_FIELD_SIZE + SVG_FIELD_SIZE / 2,
+        pos.1 * SVG_FIELD_SIZE + SVG_FIELD_SIZE / 2,
+        SVG_FIELD_SIZE as f64 * 0.4,
+        color
+    )
+}
+
+// Renders the maze as SVG. Optionally, the inspected cells are drawn as heatmap (early
+// inspected cells in the color of inspected cells, late ones in the color of the current cell)
+// and the path is drawn as line on top of it.
+pub fn maze_to_svg(
+    maze: &Maze,
+    path: Option<&[(usize, usize)]>,
+    inspection_order: Option<&[(usize, usize)]>,
+) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        maze.width * SVG_FIELD_SIZE,
+        maze.height * SVG_FIELD_SIZE
+    );
+    svg.push_str(&format!(
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        SVG_COLOR_BACKGROUND
+    ));
+
+    // Heatmap of the inspected cells.
+    if let Some(inspection_order) = inspection_order {
+        for (idx, &pos) in inspection_order.iter().enumerate() {
+            let t = idx as f64 / (inspection_order.len().max(2) - 1) as f64;
+            let color = interpolate_color(
+                CellColorType::InspectedCell.to_rgb(),
+                CellColorType::CurrentCell.to_rgb(),
+                t,
+            );
+            svg.push_str(&rect(pos, 1, &to_svg_color(color)));
+        }
+    }
+
+    // Walls. Consecutive blocked fields of a row are merged into one rectangle.
+    for (row, data_row) in maze.data.iter().enumerate() {
+        let mut col = 0;
+        while col < maze.width {
+            if data_row[col] == MAZE_VALUE_BLOCKED {
+                let length = data_row[col..]
+                    .iter()
+                    .take_while(|&&datum| datum == MAZE_VALUE_BLOCKED)
+                    .count();
+                svg.push_str(&rect((col, row), length, SVG_COLOR_WALL));
+                col += length;
+            } else {
+                col += 1;
+            }
+        }
+    }
+
+    // The path through the centers of the fields.
+    if let Some(path) = path.filter(|path| !path.is_empty()) {
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>\n",
+            path.iter()
+                .map(|pos| format!(
+                    "{},{}",
+                    pos.0 * SVG_FIELD_SIZE + SVG_FIELD_SIZE / 2,
+                    pos.1 * SVG_FIELD_SIZE + SVG_FIELD_SIZE / 2
+                ))
+                .collect::<Vec<String>>()
+                .join(" "),
+            to_svg_color(CellColorType::Path.to_rgb()),
+            SVG_FIELD_SIZE / 2
+        ));
+    }
+
+    // Start and end position.
+    svg.push_str(&circle(
+        maze.pos_start,
+        &to_svg_color(CellColorType::Path.to_rgb()),
+    ));
+    svg.push_str(&circle(
+        maze.pos_end,
+        &to_svg_color(CellColorType::CurrentCell.to_rgb()),
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
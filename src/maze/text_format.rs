@@ -0,0 +1,318 @@
+use crate::maze::draw::{
+    SYMBOL_MAZE_FIELD_ACCESSIBLE, SYMBOL_MAZE_FIELD_BLOCKED, SYMBOL_MAZE_POS_END,
+    SYMBOL_MAZE_POS_START,
+};
+use crate::maze::generator::TerrainType;
+use crate::maze::maze::*;
+use std::io::Write;
+
+// Lines starting with this character (only allowed above the grid) form the header.
+const HEADER_PREFIX: char = '#';
+const HEADER_KEY_GENERATOR: &str = "generator";
+const HEADER_KEY_SEED: &str = "seed";
+const HEADER_KEY_TERRAIN_START: &str = "start terrain";
+const HEADER_KEY_TERRAIN_END: &str = "end terrain";
+
+// Converts the maze into a text grid that looks like the maze drawn in the terminal.
+// If known, the generator and the seed are written into a header above the grid. Fields with
+// terrain are written with the symbol of the terrain (see TerrainType). The terrain beneath the
+// start and the end position goes into the header as well.
+pub fn maze_to_text(maze: &Maze) -> String {
+    let mut text = String::new();
+    if let Some(generator) = &maze.generator {
+        text.push_str(&format!(
+            "{} {}: {}\n",
+            HEADER_PREFIX, HEADER_KEY_GENERATOR, generator
+        ));
+    }
+    if let Some(seed) = maze.seed {
+        text.push_str(&format!(
+            "{} {}: {}\n",
+            HEADER_PREFIX, HEADER_KEY_SEED, seed
+        ));
+    }
+    for (key, pos) in [
+        (HEADER_KEY_TERRAIN_START, maze.pos_start),
+        (HEADER_KEY_TERRAIN_END, maze.pos_end),
+    ] {
+        if let Some(terrain_type) = TerrainType::from_cost(maze.cost(pos)) {
+            text.push_str(&format!(
+                "{} {}: {}\n",
+                HEADER_PREFIX,
+                key,
+                terrain_type.to_symbol()
+            ));
+        }
+    }
+    for (row, data_row) in maze.data.iter().enumerate() {
+        for (col, &datum) in data_row.iter().enumerate() {
+            text.push(if (col, row) == maze.pos_start {
+                SYMBOL_MAZE_POS_START
+            } else if (col, row) == maze.pos_end {
+                SYMBOL_MAZE_POS_END
+            } else if datum == MAZE_VALUE_BLOCKED {
+                SYMBOL_MAZE_FIELD_BLOCKED
+            } else if let Some(terrain_type) = TerrainType::from_cost(maze.costs[row][col]) {
+                terrain_type.to_symbol()
+            } else {
+                SYMBOL_MAZE_FIELD_ACCESSIBLE
+            });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+// Writes a maze that is created row by row (see Eller::rows) as text grid, like maze_to_text,
+// but without building the maze first. Every row is written as soon as it is created. The start
+// and the end position are the default ones (top left and bottom right cell).
+pub fn write_rows_as_text(
+    writer: &mut dyn Write,
+    rows: impl Iterator<Item = Vec<bool>>,
+    width: usize,
+    height: usize,
+    generator: &str,
+    seed: u64,
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{} {}: {}",
+        HEADER_PREFIX, HEADER_KEY_GENERATOR, generator
+    )?;
+    writeln!(writer, "{} {}: {}", HEADER_PREFIX, HEADER_KEY_SEED, seed)?;
+    let pos_start = (1, 1);
+    let pos_end = (width - 2, height - 2);
+    for (row, data_row) in rows.enumerate() {
+        let mut line: String = data_row
+            .iter()
+            .enumerate()
+            .map(|(col, &datum)| {
+                if (col, row) == pos_start {
+                    SYMBOL_MAZE_POS_START
+                } else if (col, row) == pos_end {
+                    SYMBOL_MAZE_POS_END
+                } else if datum == MAZE_VALUE_BLOCKED {
+                    SYMBOL_MAZE_FIELD_BLOCKED
+                } else {
+                    SYMBOL_MAZE_FIELD_ACCESSIBLE
+                }
+            })
+            .collect();
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+    }
+    Ok(())
+}
+
+// Reads a maze from a text grid (see maze_to_text). The header is optional. The maze needs
+// to be surrounded by blocked fields and needs to contain exactly one start and one end position.
+// The end position does not need to be reachable from the start position (the solvers find no
+// path then).
+pub fn maze_from_text(text: &str) -> Result<Maze, String> {
+    let mut lines: Vec<&str> = text
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+
+    // Read the header.
+    let mut generator: Option<String> = None;
+    let mut seed: Option<u64> = None;
+    let mut terrain_type_start: Option<TerrainType> = None;
+    let mut terrain_type_end: Option<TerrainType> = None;
+    let number_of_header_lines = lines
+        .iter()
+        .take_while(|line| line.starts_with(HEADER_PREFIX))
+        .count();
+    for (idx, line) in lines.drain(..number_of_header_lines).enumerate() {
+        let Some((key, value)) = line[HEADER_PREFIX.len_utf8()..].split_once(':') else {
+            // Lines without a key are comments.
+            continue;
+        };
+        match key.trim() {
+            HEADER_KEY_GENERATOR => generator = Some(String::from(value.trim())),
+            HEADER_KEY_SEED => {
+                seed =
+                    Some(value.trim().parse().map_err(|_| {
+                        format!("Invalid seed '{}' in line {}.", value.trim(), idx + 1)
+                    })?)
+            }
+            HEADER_KEY_TERRAIN_START | HEADER_KEY_TERRAIN_END => {
+                let mut symbols = value.trim().chars();
+                let terrain_type = match (symbols.next(), symbols.next()) {
+                    (Some(symbol), None) => TerrainType::from_symbol(symbol),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    format!("Invalid terrain '{}' in line {}.", value.trim(), idx + 1)
+                })?;
+                if key.trim() == HEADER_KEY_TERRAIN_START {
+                    terrain_type_start = Some(terrain_type);
+                } else {
+                    terrain_type_end = Some(terrain_type);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let height = lines.len();
+    let width = lines.first().map_or(0, |line| line.chars().count());
+    if width < 3 || height < 3 {
+        return Err(String::from(
+            "The maze needs to be at least 3x3 fields large.",
+        ));
+    }
+
+    let mut data: Vec<Vec<bool>> = Vec::new();
+    let mut costs: Vec<Vec<usize>> = Vec::new();
+    let mut pos_start: Option<(usize, usize)> = None;
+    let mut pos_end: Option<(usize, usize)> = None;
+    for (row, line) in lines.iter().enumerate() {
+        let line_number = number_of_header_lines + row + 1;
+        if line.chars().count() != width {
+            return Err(format!(
+                "Line {} has {} fields, expected {}.",
+                line_number,
+                line.chars().count(),
+                width
+            ));
+        }
+        let mut data_row: Vec<bool> = Vec::new();
+        let mut costs_row: Vec<usize> = Vec::new();
+        for (col, c) in line.chars().enumerate() {
+            let terrain_type = TerrainType::from_symbol(c);
+            let datum = match c {
+                _ if terrain_type.is_some() => MAZE_VALUE_ACCESSIBLE,
+                SYMBOL_MAZE_FIELD_BLOCKED => MAZE_VALUE_BLOCKED,
+                SYMBOL_MAZE_FIELD_ACCESSIBLE => MAZE_VALUE_ACCESSIBLE,
+                SYMBOL_MAZE_POS_START | SYMBOL_MAZE_POS_END => {
+                    let pos = if c == SYMBOL_MAZE_POS_START {
+                        &mut pos_start
+                    } else {
+                        &mut pos_end
+                    };
+                    if pos.is_some() {
+                        return Err(format!("The maze contains more than one '{}'.", c));
+                    }
+                    *pos = Some((col, row));
+                    MAZE_VALUE_ACCESSIBLE
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown character '{}' in line {}, column {}.",
+                        c,
+                        line_number,
+                        col + 1
+                    ))
+                }
+            };
+            let is_border = row == 0 || row == height - 1 || col == 0 || col == width - 1;
+            if is_border && datum == MAZE_VALUE_ACCESSIBLE {
+                return Err(format!(
+                    "The border of the maze is open in line {}, column {}.",
+                    line_number,
+                    col + 1
+                ));
+            }
+            data_row.push(datum);
+            costs_row
+                .push(terrain_type.map_or(MAZE_COST_DEFAULT, |terrain_type| terrain_type.cost()));
+        }
+        data.push(data_row);
+        costs.push(costs_row);
+    }
+
+    match (pos_start, pos_end) {
+        (Some(pos_start), Some(pos_end)) => {
+            let mut maze = Maze::from_data(data, pos_start, pos_end);
+            maze.costs = costs;
+            for (terrain_type, pos) in
+                [(terrain_type_start, pos_start), (terrain_type_end, pos_end)]
+            {
+                if let Some(terrain_type) = terrain_type {
+                    maze.costs[pos.1][pos.0] = terrain_type.cost();
+                }
+            }
+            // The cost of the corridors depends on the terrain.
+            maze.generate_graph();
+            maze.generator = generator;
+            maze.seed = seed;
+            Ok(maze)
+        }
+        (None, _) => Err(format!(
+            "The maze does not contain a start position '{}'.",
+            SYMBOL_MAZE_POS_START
+        )),
+        (_, None) => Err(format!(
+            "The maze does not contain an end position '{}'.",
+            SYMBOL_MAZE_POS_END
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::event::NullEventHandler;
+    use crate::maze::generator::{Braid, Eller, MazeGenerationAlgorithms, MazeGenerator, Terrain};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn maze_round_trips_through_text() {
+        for algorithm in MazeGenerationAlgorithms::all() {
+            for terrain in [false, true] {
+                let mut maze = Maze::new(31, 21, (1, 1));
+                maze.change_size(31, 21);
+                let generator = Terrain::wrap(Braid::wrap(algorithm.to_generator(), 0.5), terrain);
+                maze.generate(&*generator, 3, &mut NullEventHandler);
+
+                let text = maze_to_text(&maze);
+                let maze_read = maze_from_text(&text).unwrap();
+                assert_eq!(maze_read.data, maze.data, "{}", text);
+                assert_eq!(maze_read.costs, maze.costs, "{}", text);
+                assert_eq!(maze_read.is_node, maze.is_node, "{}", text);
+                assert_eq!(maze_read.pos_start, maze.pos_start);
+                assert_eq!(maze_read.pos_end, maze.pos_end);
+                assert_eq!(maze_read.generator, maze.generator);
+                assert_eq!(maze_read.seed, maze.seed);
+                assert_eq!(maze_to_text(&maze_read), text);
+            }
+        }
+    }
+
+    #[test]
+    fn streamed_rows_give_the_text_of_the_maze() {
+        for (width, height) in [(11, 11), (31, 21)] {
+            let mut maze = Maze::new(width, height, (1, 1));
+            maze.change_size(width, height);
+            maze.generate(&Eller, 5, &mut NullEventHandler);
+
+            let mut text: Vec<u8> = Vec::new();
+            let rows = Eller::rows(width, height, StdRng::seed_from_u64(5));
+            write_rows_as_text(&mut text, rows, width, height, &Eller.to_string(), 5).unwrap();
+            assert_eq!(String::from_utf8(text).unwrap(), maze_to_text(&maze));
+        }
+    }
+
+    #[test]
+    fn unreachable_end_position_is_loaded() {
+        let maze = maze_from_text("█████\n█S█E█\n█████\n").unwrap();
+        assert_eq!(maze.pos_start, (1, 1));
+        assert_eq!(maze.pos_end, (3, 1));
+    }
+
+    #[test]
+    fn invalid_text_is_rejected() {
+        for text in [
+            "███\n█S█\n███\n",
+            "█████\n█S E \n█████\n",
+            "█████\n█S E█\n████\n",
+            "█████\n█SXE█\n█████\n",
+            "# end terrain: X\n█████\n█S E█\n█████\n",
+        ] {
+            assert!(maze_from_text(text).is_err(), "{}", text);
+        }
+    }
+}
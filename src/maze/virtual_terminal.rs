@@ -0,0 +1,192 @@
+use std::io::{Result, Write};
+
+// The colors of the terminal if no color is set.
+pub const TERMINAL_COLOR_FOREGROUND_DEFAULT: (u8, u8, u8) = (229, 229, 229);
+pub const TERMINAL_COLOR_BACKGROUND_DEFAULT: (u8, u8, u8) = (0, 0, 0);
+
+// The 16 named colors (xterm palette), indexed by their ANSI color number.
+const TERMINAL_COLORS_NAMED: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCell {
+    pub character: char,
+    pub foreground: (u8, u8, u8),
+    pub background: (u8, u8, u8),
+}
+
+impl Default for TerminalCell {
+    fn default() -> Self {
+        TerminalCell {
+            character: ' ',
+            foreground: TERMINAL_COLOR_FOREGROUND_DEFAULT,
+            background: TERMINAL_COLOR_BACKGROUND_DEFAULT,
+        }
+    }
+}
+
+enum ParserState {
+    Text,
+    Escape,
+    ControlSequence(String),
+}
+
+// A terminal without a screen: it interprets the escape sequences written by termion (cursor
+// movement, colors, clearing) and keeps the resulting characters in a grid. This is used to
+// record the animations without a real terminal.
+pub struct VirtualTerminal {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Vec<TerminalCell>>,
+    cursor: (usize, usize),
+    foreground: (u8, u8, u8),
+    background: (u8, u8, u8),
+    state: ParserState,
+    // Bytes of a multi-byte character that is not complete yet.
+    utf8_buffer: Vec<u8>,
+}
+
+impl VirtualTerminal {
+    pub fn new(width: usize, height: usize) -> Self {
+        VirtualTerminal {
+            width,
+            height,
+            cells: vec![vec![TerminalCell::default(); width]; height],
+            cursor: (0, 0),
+            foreground: TERMINAL_COLOR_FOREGROUND_DEFAULT,
+            background: TERMINAL_COLOR_BACKGROUND_DEFAULT,
+            state: ParserState::Text,
+            utf8_buffer: Vec::new(),
+        }
+    }
+
+    fn put_character(&mut self, character: char) {
+        match character {
+            '\n' => self.cursor = (0, self.cursor.1 + 1),
+            '\r' => self.cursor.0 = 0,
+            _ => {
+                // Characters outside of the terminal are clipped.
+                if self.cursor.0 < self.width && self.cursor.1 < self.height {
+                    self.cells[self.cursor.1][self.cursor.0] = TerminalCell {
+                        character,
+                        foreground: self.foreground,
+                        background: self.background,
+                    };
+                }
+                self.cursor.0 += 1;
+            }
+        }
+    }
+
+    fn execute_control_sequence(&mut self, parameters: &str, command: char) {
+        let numbers: Vec<usize> = parameters
+            .split(';')
+            .map(|parameter| parameter.parse().unwrap_or(0))
+            .collect();
+        match command {
+            // Goto (1-based).
+            'H' => {
+                self.cursor = (
+                    numbers.get(1).unwrap_or(&1).saturating_sub(1),
+                    numbers.first().unwrap_or(&1).saturating_sub(1),
+                )
+            }
+            // Clear the whole screen.
+            'J' if numbers[0] == 2 => {
+                self.cells = vec![vec![TerminalCell::default(); self.width]; self.height]
+            }
+            'm' => self.set_graphic_rendition(&numbers),
+            // Everything else (e.g. hiding the cursor) does not change the content.
+            _ => {}
+        }
+    }
+
+    fn set_graphic_rendition(&mut self, numbers: &[usize]) {
+        let color = |numbers: &[usize]| match numbers {
+            [5, index, ..] => TERMINAL_COLORS_NAMED.get(*index).copied(),
+            [2, r, g, b, ..] => Some((*r as u8, *g as u8, *b as u8)),
+            _ => None,
+        };
+        match numbers {
+            [0] => {
+                self.foreground = TERMINAL_COLOR_FOREGROUND_DEFAULT;
+                self.background = TERMINAL_COLOR_BACKGROUND_DEFAULT;
+            }
+            [38, rest @ ..] => {
+                self.foreground = color(rest).unwrap_or(TERMINAL_COLOR_FOREGROUND_DEFAULT)
+            }
+            [48, rest @ ..] => {
+                self.background = color(rest).unwrap_or(TERMINAL_COLOR_BACKGROUND_DEFAULT)
+            }
+            [39] => self.foreground = TERMINAL_COLOR_FOREGROUND_DEFAULT,
+            [49] => self.background = TERMINAL_COLOR_BACKGROUND_DEFAULT,
+            _ => {}
+        }
+    }
+
+    fn process_byte(&mut self, byte: u8) {
+        match &mut self.state {
+            ParserState::Text => {
+                if byte == 0x1b {
+                    self.state = ParserState::Escape;
+                    return;
+                }
+                self.utf8_buffer.push(byte);
+                match std::str::from_utf8(&self.utf8_buffer) {
+                    Ok(text) => {
+                        let character = text.chars().next().unwrap();
+                        self.utf8_buffer.clear();
+                        self.put_character(character);
+                    }
+                    Err(error) if error.error_len().is_some() => self.utf8_buffer.clear(),
+                    // The character is not complete yet.
+                    Err(_) => {}
+                }
+            }
+            ParserState::Escape => {
+                self.state = if byte == b'[' {
+                    ParserState::ControlSequence(String::new())
+                } else {
+                    ParserState::Text
+                };
+            }
+            ParserState::ControlSequence(parameters) => {
+                if (0x40..=0x7e).contains(&byte) {
+                    let parameters = std::mem::take(parameters);
+                    self.state = ParserState::Text;
+                    self.execute_control_sequence(&parameters, byte as char);
+                } else {
+                    parameters.push(byte as char);
+                }
+            }
+        }
+    }
+}
+
+impl Write for VirtualTerminal {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        for &byte in buf {
+            self.process_byte(byte);
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use crate::maze::animation::animation_speed_to_string;
 use std::io::Write;
+use termion::event::Key;
 
 const TERMINAL_UI_BORDER_CORNER_TOP_LEFT: char = '╔';
 const TERMINAL_UI_BORDER_CORNER_TOP_RIGHT: char = '╗';
@@ -20,6 +22,9 @@ const TERMINAL_UI_PADDING_OUTSIDE_VERTICAL: u16 = 4;
 const TERMINAL_UI_PADDING_INSIDE_HORIZONTAL: u16 = 1;
 const TERMINAL_UI_PADDING_INSIDE_VERTICAL: u16 = 1;
 
+// The side panel (e.g. of the step debugger) needs at least this many columns.
+const TERMINAL_UI_SIDE_PANEL_WIDTH_MIN: u16 = 20;
+
 pub const TERMINAL_WIDTH_MIN: u16 = 50;
 pub const TERMINAL_HEIGHT_MIN: u16 = 40;
 
@@ -133,6 +138,7 @@ fn draw_border(screen: &mut dyn Write) {
     .unwrap();
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn print_informations(
     screen: &mut dyn Write,
     maze_dimension: (usize, usize),
@@ -140,11 +146,14 @@ pub fn print_informations(
     solving_algorithm: &str,
     number_of_inspected_cells: usize,
     animate: bool,
+    dead_end_removal: f64,
+    terrain: bool,
+    seed: u64,
 ) {
     let (width, _) = termion::terminal_size().unwrap();
     write!(
         screen,
-        "{}{}{}{}x{}, generator: {}, solver: {}, insp. cells: {}, animate: {}",
+        "{}{}{}{}x{}, generator: {}, solver: {}, insp. cells: {}, animate: {}, braid: {}, terrain: {}, seed: {}",
         termion::cursor::Goto(1, 1),
         " ".repeat(width as usize),
         termion::cursor::Goto(1, 1),
@@ -153,7 +162,18 @@ pub fn print_informations(
         generation_algorithm,
         solving_algorithm,
         number_of_inspected_cells,
-        if animate { "ON" } else { "OFF" }
+        if animate {
+            format!("ON ({})", animation_speed_to_string())
+        } else {
+            String::from("OFF")
+        },
+        if dead_end_removal > 0.0 {
+            format!("{}%", (dead_end_removal * 100.0).round())
+        } else {
+            String::from("OFF")
+        },
+        if terrain { "ON" } else { "OFF" },
+        seed
     )
     .unwrap();
     screen.flush().unwrap();
@@ -172,3 +192,59 @@ pub fn print_solving_sequence(screen: &mut dyn Write, solving_sequence: String)
     .unwrap();
     screen.flush().unwrap();
 }
+
+// Prints the lines in the draw area, starting at the column left (e.g. right of the maze) up to
+// the border. Lines that do not fit are cut off. Returns false if there is not enough space.
+pub fn print_side_panel(screen: &mut dyn Write, left: u16, lines: &[String]) -> bool {
+    let (width, height) = termion::terminal_size().unwrap();
+    let right =
+        width - TERMINAL_UI_PADDING_OUTSIDE_HORIZONTAL - TERMINAL_UI_PADDING_INSIDE_HORIZONTAL;
+    let top = TERMINAL_UI_PADDING_OUTSIDE_VERTICAL + TERMINAL_UI_PADDING_INSIDE_VERTICAL + 2;
+    let bottom =
+        height - TERMINAL_UI_PADDING_OUTSIDE_VERTICAL - TERMINAL_UI_PADDING_INSIDE_VERTICAL;
+    if right.saturating_sub(left) < TERMINAL_UI_SIDE_PANEL_WIDTH_MIN {
+        return false;
+    }
+    let panel_width = (right - left) as usize;
+    let panel_height = (bottom - top) as usize;
+    for row in 0..panel_height {
+        let line = if row + 1 == panel_height && lines.len() > panel_height {
+            format!("... ({} more)", lines.len() - row)
+        } else {
+            lines.get(row).cloned().unwrap_or_default()
+        };
+        write!(
+            screen,
+            "{}{:<width$}",
+            termion::cursor::Goto(left, top + row as u16),
+            line.chars().take(panel_width).collect::<String>(),
+            width = panel_width
+        )
+        .unwrap();
+    }
+    screen.flush().unwrap();
+    true
+}
+
+// Reads a line of input in the area of the solving sequence. Enter confirms, Esc aborts.
+pub fn read_input(
+    screen: &mut dyn Write,
+    keys: &mut dyn Iterator<Item = std::io::Result<Key>>,
+    prompt: &str,
+) -> Option<String> {
+    let mut input = String::new();
+    let result = loop {
+        print_solving_sequence(screen, format!("{}{}", prompt, input));
+        match keys.next() {
+            Some(Ok(Key::Char('\n'))) => break Some(input),
+            Some(Ok(Key::Esc)) | None => break None,
+            Some(Ok(Key::Backspace)) => {
+                input.pop();
+            }
+            Some(Ok(Key::Char(c))) => input.push(c),
+            _ => {}
+        }
+    };
+    print_solving_sequence(screen, String::new());
+    result
+}